@@ -0,0 +1,250 @@
+//! Filters for events polled from the event loop.
+//!
+//! An [`EventFilter`](./struct.EventFilter.html) sits between
+//! `WindowEvents::next` and the event handlers of an application.
+//! It drops events by id and reduces bursts of motion and resize events,
+//! which some back-ends send many times per frame.
+
+use std::collections::VecDeque;
+use input::{ Event, EventId, GenericEvent, MouseCursorEvent, MouseRelativeArgs,
+             MouseRelativeEvent, ResizeEvent };
+use window::Window;
+use WindowEvents;
+
+/// Filters events polled from the event loop.
+///
+/// - Ignored events are dropped
+/// - With motion coalescing, motion events are held until another event arrives,
+///   keeping only the last mouse cursor event and summing relative mouse motions
+///   per device, even when cursor and relative events are interleaved
+/// - With resize debouncing, resize events are held back until the next
+///   render or update event, and only the last one is emitted
+///
+/// Other events keep their order.
+/// When the event loop ends, all held events are emitted.
+#[derive(Clone, Debug)]
+pub struct EventFilter<E> {
+    ignored: Vec<EventId>,
+    coalesce_motion: bool,
+    debounce_resize: bool,
+    motion: Vec<Event<E>>,
+    resize: Option<Event<E>>,
+    ready: VecDeque<Event<E>>,
+}
+
+impl<E: GenericEvent> EventFilter<E> {
+    /// Creates a new filter that lets all events through.
+    pub fn new() -> EventFilter<E> {
+        EventFilter {
+            ignored: vec![],
+            coalesce_motion: false,
+            debounce_resize: false,
+            motion: vec![],
+            resize: None,
+            ready: VecDeque::new(),
+        }
+    }
+
+    /// Drops events with the id.
+    pub fn ignore(mut self, event_id: EventId) -> Self {
+        self.ignored.push(event_id);
+        self
+    }
+
+    /// Enable or disable coalescing of mouse cursor and relative motion events.
+    ///
+    /// Motion is coalesced separately for each device.
+    pub fn coalesce_motion(mut self, enable: bool) -> Self {
+        self.coalesce_motion = enable;
+        self
+    }
+
+    /// Enable or disable debouncing of resize events.
+    pub fn debounce_resize(mut self, enable: bool) -> Self {
+        self.debounce_resize = enable;
+        self
+    }
+
+    /// Returns the next filtered event.
+    pub fn next<W>(&mut self, events: &mut WindowEvents, window: &mut W) -> Option<Event<E>>
+        where W: Window<Event = E>
+    {
+        self.next_with(|| events.next(window))
+    }
+
+    /// Returns the next filtered event, mapping input events to another type.
+    ///
+    /// Input events are dropped when the closure returns `None`.
+    pub fn next_map<W, J, F>(&mut self, events: &mut WindowEvents, window: &mut W, mut f: F)
+        -> Option<Event<J>>
+        where W: Window<Event = E>, F: FnMut(E) -> Option<J>
+    {
+        loop {
+            let e = match self.next(events, window) {
+                Some(e) => e,
+                None => return None,
+            };
+            if let Some(e) = e.filter_map_input(&mut f) {
+                return Some(e);
+            }
+        }
+    }
+
+    // Filters events, using a closure to poll unfiltered events.
+    fn next_with<P>(&mut self, mut poll: P) -> Option<Event<E>>
+        where P: FnMut() -> Option<Event<E>>
+    {
+        loop {
+            if let Some(e) = self.ready.pop_front() {
+                return Some(e);
+            }
+            let e = match poll() {
+                Some(e) => e,
+                None => {
+                    // Emit held events before ending.
+                    self.ready.extend(self.motion.drain(..));
+                    self.ready.extend(self.resize.take());
+                    return self.ready.pop_front();
+                }
+            };
+            if self.ignored.iter().any(|&id| id == e.event_id()) {
+                continue;
+            }
+            if self.debounce_resize && e.try_resize_args().unwrap_or(None).is_some() {
+                self.resize = Some(e);
+                continue;
+            }
+            let e = if self.coalesce_motion {
+                match self.hold_motion(e) {
+                    Some(e) => e,
+                    None => continue,
+                }
+            } else {
+                e
+            };
+            self.ready.extend(self.motion.drain(..));
+            match e {
+                Event::Input(_) => {}
+                _ => if let Some(resize) = self.resize.take() {
+                    self.ready.push_back(resize);
+                }
+            }
+            self.ready.push_back(e);
+        }
+    }
+
+    // Holds a motion event, merging it with held motion of the same kind and device.
+    //
+    // Returns the event if it is not a motion event.
+    fn hold_motion(&mut self, e: Event<E>) -> Option<Event<E>> {
+        if let Ok(Some(args)) = e.try_mouse_cursor_full_args() {
+            let held = self.motion.iter().position(|held|
+                match held.try_mouse_cursor_full_args() {
+                    Ok(Some(pos)) => pos.device == args.device,
+                    _ => false,
+                });
+            match held {
+                Some(i) => self.motion[i] = e,
+                None => self.motion.push(e),
+            }
+            return None;
+        }
+        let args = match e.try_mouse_relative_full_args() {
+            Ok(Some(args)) => args,
+            _ => return Some(e),
+        };
+        let held = self.motion.iter().position(|held|
+            match held.try_mouse_relative_full_args() {
+                Ok(Some(sum)) => sum.device == args.device,
+                _ => false,
+            });
+        let merged = held.and_then(|i| {
+            let sum = match self.motion[i].try_mouse_relative_full_args() {
+                Ok(Some(sum)) => sum,
+                _ => return None,
+            };
+            let sum = MouseRelativeArgs::new(sum.x + args.x, sum.y + args.y)
+                .device(args.device);
+            MouseRelativeEvent::from_mouse_relative_args(sum, &e).map(|merged| (i, merged))
+        });
+        match merged {
+            Some((i, merged)) => self.motion[i] = merged,
+            None => self.motion.push(e),
+        }
+        None
+    }
+}
+
+impl<E: GenericEvent> Default for EventFilter<E> {
+    fn default() -> EventFilter<E> { EventFilter::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        use input::{ Button, Input, Key, Motion, MouseRelativeArgs, UpdateArgs, TEXT };
+
+        let cursor = |x: f64| -> Event {
+            Event::Input(Input::Move(Motion::MouseCursor((x, 0.0).into())))
+        };
+        let relative = |x: f64| -> Event {
+            Event::Input(Input::Move(Motion::MouseRelative(MouseRelativeArgs::new(x, x))))
+        };
+        let press: Event = Event::Input(Input::Press(Button::Keyboard(Key::A).into()));
+        let update: Event = Event::Update(UpdateArgs { dt: 0.0 });
+        let mut events = vec![
+            cursor(1.0), cursor(2.0), Event::Input(Input::Resize(10, 10)),
+            cursor(3.0), relative(1.0), relative(2.0),
+            press.clone(), Event::Input(Input::Text("a".into())),
+            Event::Input(Input::Resize(20, 20)), update.clone(),
+        ].into_iter();
+        let mut filter = EventFilter::new()
+            .ignore(TEXT)
+            .coalesce_motion(true)
+            .debounce_resize(true);
+        let mut res = vec![];
+        while let Some(e) = filter.next_with(|| events.next()) {
+            res.push(e);
+        }
+        assert_eq!(res, vec![
+            cursor(3.0), relative(3.0), press,
+            Event::Input(Input::Resize(20, 20)), update,
+        ]);
+    }
+
+    #[test]
+    fn test_filter_interleaved_motion() {
+        use input::{ Button, Input, Key, Motion, MouseCursorArgs, MouseRelativeArgs };
+
+        let cursor = |x: f64| -> Event {
+            Event::Input(Input::Move(Motion::MouseCursor((x, 0.0).into())))
+        };
+        let cursor_device = |x: f64, device: i64| -> Event {
+            Event::Input(Input::Move(Motion::MouseCursor(
+                MouseCursorArgs::new(x, 0.0).device(device))))
+        };
+        let relative = |x: f64, device: i64| -> Event {
+            Event::Input(Input::Move(Motion::MouseRelative(
+                MouseRelativeArgs::new(x, x).device(device))))
+        };
+        let press: Event = Event::Input(Input::Press(Button::Keyboard(Key::A).into()));
+        let mut events = vec![
+            cursor(1.0), relative(1.0, 0), cursor(2.0), relative(2.0, 1), relative(2.0, 0),
+            cursor_device(4.0, 1), cursor_device(5.0, 1), press.clone(),
+            relative(1.0, 0), cursor(3.0), Event::Input(Input::Resize(20, 20)), relative(1.0, 0),
+        ].into_iter();
+        let mut filter = EventFilter::new().coalesce_motion(true).debounce_resize(true);
+        let mut res = vec![];
+        while let Some(e) = filter.next_with(|| events.next()) {
+            res.push(e);
+        }
+        // Held events are emitted when polling ends.
+        assert_eq!(res, vec![
+            cursor(2.0), relative(3.0, 0), relative(2.0, 1), cursor_device(5.0, 1), press,
+            relative(2.0, 0), cursor(3.0), Event::Input(Input::Resize(20, 20)),
+        ]);
+    }
+}
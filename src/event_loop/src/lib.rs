@@ -7,12 +7,19 @@ extern crate window;
 extern crate input;
 extern crate viewport;
 extern crate time;
+extern crate rustc_serialize;
 
 use std::thread::sleep;
 use std::time::Duration;
 use std::cmp;
 use window::Window;
-use input::{ AfterRenderArgs, Event, IdleArgs, RenderArgs, UpdateArgs };
+use input::{ AfterRenderArgs, Event, IdleArgs, RenderArgs, Timestamped, UpdateArgs };
+
+pub use filter::EventFilter;
+pub use record::{ read_recording, RecordedInput, Recorder, ReplayWindow };
+
+mod filter;
+mod record;
 
 /// A trait for create event iterator from window.
 pub trait Events {
@@ -160,6 +167,29 @@ impl WindowEvents
     /// Returns the next game event.
     pub fn next<W>(&mut self, window: &mut W) -> Option<Event<W::Event>>
         where W: Window
+    {
+        self.next_with(window, |window| window.poll_event())
+    }
+
+    /// Returns the next game event, with timestamps on input events.
+    ///
+    /// Input events without a timestamp from the window
+    /// are stamped with the time they were polled.
+    pub fn next_timestamped<W>(&mut self, window: &mut W)
+        -> Option<Event<Timestamped<W::Event>>>
+        where W: Window
+    {
+        self.next_with(window, |window| {
+            window.poll_event_timestamped().map(|(e, timestamp)| {
+                let timestamp = timestamp.unwrap_or_else(time::precise_time_ns);
+                Timestamped::new(e, timestamp)
+            })
+        })
+    }
+
+    // Runs the event loop, using a closure to poll input events.
+    fn next_with<W, I, P>(&mut self, window: &mut W, mut poll: P) -> Option<Event<I>>
+        where W: Window, P: FnMut(&mut W) -> Option<I>
     {
         loop {
             if window.should_close() { return None; }
@@ -168,7 +198,7 @@ impl WindowEvents
                     // Handle input events before rendering,
                     // because window might be closed and destroy
                     // the graphics context.
-                    if let Some(e) = window.poll_event() {
+                    if let Some(e) = poll(window) {
                         return Some(Event::Input(e));
                     }
                     if window.should_close() {
@@ -229,7 +259,7 @@ impl WindowEvents
                         let next_update = self.last_update + self.dt_update_in_ns;
                         let next_event = cmp::min(next_frame, next_update);
                         if next_event > current_time {
-                            if let Some(x) = window.poll_event() {
+                            if let Some(x) = poll(window) {
                                 *idle = Idle::No;
                                 return Some(Event::Input(x));
                             } else if *idle == Idle::No {
@@ -252,13 +282,13 @@ impl WindowEvents
                 State::HandleEvents(update_state) => {
                     if self.bench_mode {
                         // Ignore input to prevent it affecting the benchmark.
-                        match window.poll_event() {
+                        match poll(window) {
                             None => State::Update(update_state),
                             Some(_) => State::HandleEvents(update_state),
                         }
                     } else {
                         // Handle all events before updating.
-                        match window.poll_event() {
+                        match poll(window) {
                             None => State::Update(update_state),
                             Some(x) => { return Some(Event::Input(x)); },
                         }
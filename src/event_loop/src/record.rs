@@ -0,0 +1,182 @@
+//! Recording and replay of input events.
+//!
+//! A [`Recorder`](./struct.Recorder.html) writes every input event together
+//! with the time since recording started, one JSON object per line.
+//! Because each event is written when it happens, a session is not lost
+//! if the application crashes.
+//!
+//! A [`ReplayWindow`](./struct.ReplayWindow.html) reads such a recording
+//! and feeds the input events back in order, at the recorded times.
+//! Render, update and idle events are not recorded,
+//! since the event loop generates them on replay.
+
+use std::collections::VecDeque;
+use std::io::{ self, BufRead, Write };
+use input::{ Event, Input };
+use rustc_serialize::json;
+use window::{ Size, Window, WindowSettings };
+use time;
+
+/// An input event with the time it happened.
+#[derive(Clone, RustcDecodable, RustcEncodable, PartialEq, Debug)]
+pub struct RecordedInput {
+    /// Time in nanoseconds since start of recording.
+    pub time: u64,
+    /// The input event.
+    pub input: Input,
+}
+
+/// Writes input events to a recording.
+pub struct Recorder<W: Write> {
+    writer: W,
+    start: u64,
+}
+
+impl<W: Write> Recorder<W> {
+    /// Creates a new recorder.
+    ///
+    /// The recorded times are relative to when this is called.
+    pub fn new(writer: W) -> Recorder<W> {
+        Recorder {
+            writer: writer,
+            start: time::precise_time_ns(),
+        }
+    }
+
+    /// Records an event if it is an input event.
+    pub fn event(&mut self, e: &Event<Input>) -> io::Result<()> {
+        if let &Event::Input(ref input) = e {
+            let recorded = RecordedInput {
+                time: time::precise_time_ns() - self.start,
+                input: input.clone(),
+            };
+            try!(self.record(&recorded));
+        }
+        Ok(())
+    }
+
+    /// Writes a recorded input.
+    pub fn record(&mut self, recorded: &RecordedInput) -> io::Result<()> {
+        let line = try!(json::encode(recorded).map_err(|err|
+            io::Error::new(io::ErrorKind::InvalidData, err)));
+        try!(writeln!(self.writer, "{}", line));
+        self.writer.flush()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Reads a recording written by `Recorder`.
+///
+/// Empty lines are ignored.
+pub fn read_recording<R: BufRead>(reader: R) -> io::Result<Vec<RecordedInput>> {
+    let mut res = vec![];
+    for line in reader.lines() {
+        let line = try!(line);
+        if line.trim().is_empty() { continue; }
+        let recorded: RecordedInput = try!(json::decode(&line).map_err(|err|
+            io::Error::new(io::ErrorKind::InvalidData, err)));
+        res.push(recorded);
+    }
+    Ok(res)
+}
+
+/// A window that replays recorded input events.
+///
+/// The clock starts at the first call to `poll_event`.
+/// An event is returned when its recorded time has passed.
+/// Resize events change the size of the window.
+pub struct ReplayWindow {
+    inputs: VecDeque<RecordedInput>,
+    start: Option<u64>,
+    should_close: bool,
+    size: Size,
+}
+
+impl ReplayWindow {
+    /// Creates a new replay window.
+    pub fn new(recording: Vec<RecordedInput>, settings: &WindowSettings) -> ReplayWindow {
+        ReplayWindow {
+            inputs: recording.into_iter().collect(),
+            start: None,
+            should_close: false,
+            size: settings.get_size(),
+        }
+    }
+
+    /// Returns true if all recorded events have been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.inputs.is_empty()
+    }
+}
+
+impl Window for ReplayWindow {
+    type Event = Input;
+
+    fn should_close(&self) -> bool { self.should_close }
+
+    fn set_should_close(&mut self, value: bool) { self.should_close = value; }
+
+    fn size(&self) -> Size { self.size }
+
+    fn swap_buffers(&mut self) {}
+
+    fn poll_event(&mut self) -> Option<Input> {
+        let now = time::precise_time_ns();
+        let start = match self.start {
+            Some(start) => start,
+            None => {
+                self.start = Some(now);
+                now
+            }
+        };
+        match self.inputs.front() {
+            Some(recorded) if recorded.time <= now - start => {}
+            _ => { return None; }
+        }
+        let input = self.inputs.pop_front().unwrap().input;
+        if let Input::Resize(w, h) = input {
+            self.size = Size { width: w, height: h };
+        }
+        Some(input)
+    }
+
+    fn draw_size(&self) -> Size { self.size() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_replay() {
+        use input::{ Button, Event, Input, Key };
+        use window::{ Window, WindowSettings };
+
+        let mut recorder = Recorder::new(vec![]);
+        recorder.event(&Event::Input(Input::Press(Button::Keyboard(Key::A).into()))).unwrap();
+        recorder.event(&Event::Input(Input::Resize(20, 10))).unwrap();
+        recorder.event(&Event::Input(Input::Release(Button::Keyboard(Key::A).into()))).unwrap();
+        let data = recorder.into_inner();
+
+        let recording = read_recording(&data[..]).unwrap();
+        assert_eq!(recording.len(), 3);
+        let mut window = ReplayWindow::new(recording,
+            &WindowSettings::new("replay", [0, 0]));
+        let mut inputs = vec![];
+        while !window.is_finished() {
+            if let Some(input) = window.poll_event() {
+                inputs.push(input);
+            }
+        }
+        assert_eq!(inputs, vec![
+            Input::Press(Button::Keyboard(Key::A).into()),
+            Input::Resize(20, 10),
+            Input::Release(Button::Keyboard(Key::A).into()),
+        ]);
+        assert_eq!(window.size().width, 20);
+    }
+}
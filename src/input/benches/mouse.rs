@@ -11,12 +11,14 @@ use input::{
     Motion,
     MouseScrollEvent,
     MouseRelativeEvent,
-    MouseCursorEvent
+    MouseCursorEvent,
+    MouseRelativeArgs,
+    ScrollArgs
 };
 
 #[bench]
 fn bench_input_mouse_cursor(bencher: &mut Bencher) {
-    let e = Input::Move(Motion::MouseCursor(0.0, 0.0));
+    let e = Input::Move(Motion::MouseCursor((0.0, 0.0).into()));
     bencher.iter(|| {
         let _: Option<Input> = MouseCursorEvent::from_xy(1.0, 0.0, &e);
     });
@@ -24,7 +26,7 @@ fn bench_input_mouse_cursor(bencher: &mut Bencher) {
 
 #[bench]
 fn bench_event_mouse_cursor(bencher: &mut Bencher) {
-    let e = Event::Input(Input::Move(Motion::MouseCursor(0.0, 0.0)));
+    let e = Event::Input(Input::Move(Motion::MouseCursor((0.0, 0.0).into())));
     bencher.iter(|| {
         let _: Option<Event> = MouseCursorEvent::from_xy(1.0, 0.0, &e);
     });
@@ -32,7 +34,7 @@ fn bench_event_mouse_cursor(bencher: &mut Bencher) {
 
 #[bench]
 fn bench_input_mouse_relative(bencher: &mut Bencher) {
-    let e = Input::Move(Motion::MouseRelative(0.0, 0.0));
+    let e = Input::Move(Motion::MouseRelative(MouseRelativeArgs::new(0.0, 0.0)));
     bencher.iter(|| {
         let _: Option<Input> = MouseRelativeEvent::from_xy(1.0, 0.0, &e);
     });
@@ -40,7 +42,7 @@ fn bench_input_mouse_relative(bencher: &mut Bencher) {
 
 #[bench]
 fn bench_event_mouse_relative(bencher: &mut Bencher) {
-    let e = Event::Input(Input::Move(Motion::MouseRelative(0.0, 0.0)));
+    let e = Event::Input(Input::Move(Motion::MouseRelative(MouseRelativeArgs::new(0.0, 0.0))));
     bencher.iter(|| {
         let _: Option<Event> = MouseRelativeEvent::from_xy(1.0, 0.0, &e);
     });
@@ -48,7 +50,7 @@ fn bench_event_mouse_relative(bencher: &mut Bencher) {
 
 #[bench]
 fn bench_input_mouse_scroll(bencher: &mut Bencher) {
-    let e = Input::Move(Motion::MouseScroll(0.0, 0.0));
+    let e = Input::Move(Motion::MouseScroll(ScrollArgs::new(0.0, 0.0)));
     bencher.iter(|| {
         let _: Option<Input> = MouseScrollEvent::from_xy(1.0, 0.0, &e);
     });
@@ -56,7 +58,7 @@ fn bench_input_mouse_scroll(bencher: &mut Bencher) {
 
 #[bench]
 fn bench_event_mouse_scroll(bencher: &mut Bencher) {
-    let e = Event::Input(Input::Move(Motion::MouseScroll(0.0, 0.0)));
+    let e = Event::Input(Input::Move(Motion::MouseScroll(ScrollArgs::new(0.0, 0.0))));
     bencher.iter(|| {
         let _: Option<Event> = MouseScrollEvent::from_xy(1.0, 0.0, &e);
     });
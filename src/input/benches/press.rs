@@ -9,7 +9,7 @@ use input::{ Button, Event, Key, Input, PressEvent };
 
 #[bench]
 fn bench_input_press(bencher: &mut Bencher) {
-    let e = Input::Press(Button::Keyboard(Key::S));
+    let e = Input::Press(Button::Keyboard(Key::S).into());
     let button = Button::Keyboard(Key::A);
     bencher.iter(|| {
         let _: Option<Input> = PressEvent::from_button(button, &e);
@@ -18,7 +18,7 @@ fn bench_input_press(bencher: &mut Bencher) {
 
 #[bench]
 fn bench_event_press(bencher: &mut Bencher) {
-    let e = Event::Input(Input::Press(Button::Keyboard(Key::S)));
+    let e = Event::Input(Input::Press(Button::Keyboard(Key::S).into()));
     let button = Button::Keyboard(Key::A);
     bencher.iter(|| {
         let _: Option<Event> = PressEvent::from_button(button, &e);
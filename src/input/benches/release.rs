@@ -9,7 +9,7 @@ use input::{ Button, Event, Key, Input, ReleaseEvent };
 
 #[bench]
 fn bench_input_release(bencher: &mut Bencher) {
-    let e = Input::Release(Button::Keyboard(Key::S));
+    let e = Input::Release(Button::Keyboard(Key::S).into());
     let button = Button::Keyboard(Key::A);
     bencher.iter(|| {
         let _: Option<Input> = ReleaseEvent::from_button(button, &e);
@@ -18,7 +18,7 @@ fn bench_input_release(bencher: &mut Bencher) {
 
 #[bench]
 fn bench_event_release(bencher: &mut Bencher) {
-    let e = Event::Input(Input::Release(Button::Keyboard(Key::S)));
+    let e = Event::Input(Input::Release(Button::Keyboard(Key::S).into()));
     let button = Button::Keyboard(Key::A);
     bencher.iter(|| {
         let _: Option<Event> = ReleaseEvent::from_button(button, &e);
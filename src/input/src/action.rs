@@ -0,0 +1,472 @@
+//! Maps buttons and axes to named actions.
+//!
+//! Games declare actions such as "jump" or "move_x" and bind them
+//! to keys, mouse buttons, controller buttons or controller axes.
+//! The bindings can be saved to and loaded from JSON,
+//! so players can change the controls.
+
+use std::collections::{ HashMap, HashSet };
+use std::fmt;
+use std::str::FromStr;
+use rustc_serialize::json;
+
+use { Button, GenericEvent };
+use keyboard::{ ModifierKey, Scancode, NO_MODIFIER };
+
+/// Input that triggers an action.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq, Debug)]
+pub enum Trigger {
+    /// A button pressed while exactly these modifier keys are held down.
+    Button(Button, ModifierKey),
+    /// A key at a physical position, pressed while exactly
+    /// these modifier keys are held down.
+    ///
+    /// Use this to bind keys by position, independent of the keyboard layout.
+    Scancode(Scancode, ModifierKey),
+    /// A controller axis, identified by controller id and axis.
+    ControllerAxis(i32, u8),
+}
+
+/// Formats the trigger for display in menus and settings files,
+/// for example `Ctrl+Shift+F5`, `Mouse Left`, `Pad0 Button3`,
+/// `Scancode W` or `Pad0 Axis1`.
+impl fmt::Display for Trigger {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Trigger::Button(button, modifiers) => {
+                if modifiers != NO_MODIFIER { try!(write!(f, "{}+", modifiers)); }
+                write!(f, "{}", button)
+            }
+            Trigger::Scancode(scancode, modifiers) => {
+                if modifiers != NO_MODIFIER { try!(write!(f, "{}+", modifiers)); }
+                write!(f, "Scancode {}", scancode)
+            }
+            Trigger::ControllerAxis(id, axis) => write!(f, "Pad{} Axis{}", id, axis),
+        }
+    }
+}
+
+impl FromStr for Trigger {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Trigger, String> {
+        if s.starts_with("Pad") && s.contains(" Axis") {
+            let mut parts = s.splitn(2, " Axis");
+            let id = parts.next().unwrap()[3..].parse();
+            let axis = parts.next().unwrap().parse();
+            return match (id, axis) {
+                (Ok(id), Ok(axis)) => Ok(Trigger::ControllerAxis(id, axis)),
+                _ => Err(format!("Expected `Pad<id> Axis<axis>`, found `{}`", s))
+            };
+        }
+        // Modifiers come before the last `+`.
+        let mut parts = s.rsplitn(2, '+');
+        let button = parts.next().unwrap();
+        let modifiers = match parts.next() {
+            Some(modifiers) => try!(modifiers.parse()),
+            None => NO_MODIFIER
+        };
+        if button.starts_with("Scancode ") {
+            Ok(Trigger::Scancode(try!(button[9..].parse()), modifiers))
+        } else {
+            Ok(Trigger::Button(try!(button.parse()), modifiers))
+        }
+    }
+}
+
+/// Binds a trigger to an action.
+#[derive(Clone, RustcDecodable, RustcEncodable, PartialEq, Debug)]
+pub struct Binding {
+    /// The name of the action.
+    pub action: String,
+    /// The input that triggers the action.
+    pub trigger: Trigger,
+    /// Multiplied with the trigger value, which is 1 for a held button
+    /// and the position for an axis.
+    pub scale: f64,
+}
+
+impl Binding {
+    /// Creates a binding to a button.
+    pub fn button<A, B>(action: A, button: B) -> Binding
+        where A: Into<String>, B: Into<Button>
+    {
+        Binding::button_with_modifiers(action, button, NO_MODIFIER)
+    }
+
+    /// Creates a binding to a button combined with modifier keys.
+    pub fn button_with_modifiers<A, B>(
+        action: A,
+        button: B,
+        modifiers: ModifierKey
+    ) -> Binding
+        where A: Into<String>, B: Into<Button>
+    {
+        Binding {
+            action: action.into(),
+            trigger: Trigger::Button(button.into(), modifiers),
+            scale: 1.0,
+        }
+    }
+
+    /// Creates a binding to a key at a physical position.
+    pub fn scancode<A: Into<String>>(action: A, scancode: Scancode) -> Binding {
+        Binding::scancode_with_modifiers(action, scancode, NO_MODIFIER)
+    }
+
+    /// Creates a binding to a key at a physical position
+    /// combined with modifier keys.
+    pub fn scancode_with_modifiers<A: Into<String>>(
+        action: A,
+        scancode: Scancode,
+        modifiers: ModifierKey
+    ) -> Binding {
+        Binding {
+            action: action.into(),
+            trigger: Trigger::Scancode(scancode, modifiers),
+            scale: 1.0,
+        }
+    }
+
+    /// Creates a binding to a controller axis.
+    pub fn controller_axis<A: Into<String>>(action: A, id: i32, axis: u8) -> Binding {
+        Binding {
+            action: action.into(),
+            trigger: Trigger::ControllerAxis(id, axis),
+            scale: 1.0,
+        }
+    }
+
+    /// Sets the scale of the binding.
+    ///
+    /// For example, bind one key with scale -1.0 and another with 1.0
+    /// to move along an axis.
+    pub fn scale(mut self, scale: f64) -> Binding {
+        self.scale = scale;
+        self
+    }
+}
+
+/// Changes to the state of an action.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ActionEvent {
+    /// A button bound to the action was pressed,
+    /// and no other button bound to it was held down.
+    Press(String),
+    /// The last button held down for the action was released.
+    Release(String),
+    /// The value of the action changed.
+    Value(String, f64),
+}
+
+/// Turns input events into action events.
+#[derive(Clone, Debug)]
+pub struct ActionMap {
+    bindings: Vec<Binding>,
+    modifiers: ModifierKey,
+    // The value of each binding, in the same order as `bindings`.
+    binding_values: Vec<f64>,
+    pressed: HashSet<String>,
+    values: HashMap<String, f64>,
+}
+
+impl ActionMap {
+    /// Creates a new action map.
+    pub fn new(bindings: Vec<Binding>) -> ActionMap {
+        let n = bindings.len();
+        ActionMap {
+            bindings: bindings,
+            modifiers: NO_MODIFIER,
+            binding_values: vec![0.0; n],
+            pressed: HashSet::new(),
+            values: HashMap::new(),
+        }
+    }
+
+    /// Loads bindings from JSON.
+    pub fn from_json(text: &str) -> Result<ActionMap, json::DecoderError> {
+        let bindings: Vec<Binding> = try!(json::decode(text));
+        Ok(ActionMap::new(bindings))
+    }
+
+    /// Saves bindings as JSON.
+    pub fn to_json(&self) -> String {
+        format!("{}", json::as_pretty_json(&self.bindings))
+    }
+
+    /// Gets the bindings.
+    pub fn bindings(&self) -> &[Binding] {
+        &self.bindings
+    }
+
+    /// Sets the bindings.
+    ///
+    /// This resets the state of all actions without emitting events.
+    pub fn set_bindings(&mut self, bindings: Vec<Binding>) {
+        *self = ActionMap {
+            modifiers: self.modifiers,
+            ..ActionMap::new(bindings)
+        };
+    }
+
+    /// Returns true if a button bound to the action is held down.
+    pub fn is_pressed(&self, action: &str) -> bool {
+        self.pressed.contains(action)
+    }
+
+    /// Returns the value of an action.
+    ///
+    /// This is the sum of the scaled values of all its bindings.
+    pub fn value(&self, action: &str) -> f64 {
+        self.values.get(action).map(|&x| x).unwrap_or(0.0)
+    }
+
+    /// Change action state depending on event.
+    ///
+    /// Calls closure for every action that changed.
+    /// When the window loses focus, all buttons are released.
+    /// When a controller is disconnected, its buttons and axes are reset.
+    /// Events with arguments of an unexpected type are ignored.
+    pub fn event<E, F>(&mut self, e: &E, mut f: F)
+        where E: GenericEvent, F: FnMut(ActionEvent)
+    {
+        use { ControllerAxisEvent, ControllerConnection, ControllerConnectionEvent,
+              FocusEvent, PressEvent, ReleaseEvent };
+
+        let mut changed = false;
+        if let Ok(Some(args)) = e.try_press_button_args() {
+            for (i, binding) in self.bindings.iter().enumerate() {
+                let matches = match binding.trigger {
+                    Trigger::Button(b, modifiers) =>
+                        b == args.button && modifiers == self.modifiers,
+                    Trigger::Scancode(s, modifiers) =>
+                        Some(s) == args.scancode && modifiers == self.modifiers,
+                    Trigger::ControllerAxis(_, _) => false,
+                };
+                if matches {
+                    self.binding_values[i] = binding.scale;
+                    changed = true;
+                }
+            }
+        }
+        if let Ok(Some(args)) = e.try_release_button_args() {
+            for (i, binding) in self.bindings.iter().enumerate() {
+                let matches = match binding.trigger {
+                    Trigger::Button(b, _) => b == args.button,
+                    Trigger::Scancode(s, _) => Some(s) == args.scancode,
+                    Trigger::ControllerAxis(_, _) => false,
+                };
+                if matches {
+                    self.binding_values[i] = 0.0;
+                    changed = true;
+                }
+            }
+        }
+        if let Ok(Some(args)) = e.try_controller_axis_args() {
+            for (i, binding) in self.bindings.iter().enumerate() {
+                if binding.trigger == Trigger::ControllerAxis(args.id, args.axis) {
+                    self.binding_values[i] = args.position * binding.scale;
+                    changed = true;
+                }
+            }
+        }
+        if let Ok(Some(false)) = e.try_focus_args() {
+            for (i, binding) in self.bindings.iter().enumerate() {
+                if is_button(binding.trigger) {
+                    self.binding_values[i] = 0.0;
+                    changed = true;
+                }
+            }
+        }
+        if let Ok(Some(ControllerConnection::Disconnected { id })) =
+            e.try_controller_connection_args() {
+            for (i, binding) in self.bindings.iter().enumerate() {
+                let controller = match binding.trigger {
+                    Trigger::Button(Button::Controller(b), _) => b.id,
+                    Trigger::ControllerAxis(controller, _) => controller,
+                    _ => { continue; }
+                };
+                if controller == id {
+                    self.binding_values[i] = 0.0;
+                    changed = true;
+                }
+            }
+        }
+        // Modifiers are updated after the bindings,
+        // so pressing a modifier key is not affected by itself.
+        self.modifiers.event(e);
+
+        if changed {
+            self.emit(&mut f);
+        }
+    }
+
+    // Compares the new state of each action with the old one.
+    fn emit<F>(&mut self, f: &mut F)
+        where F: FnMut(ActionEvent)
+    {
+        let mut visited: HashSet<&str> = HashSet::new();
+        for binding in &self.bindings {
+            let action = &binding.action[..];
+            if !visited.insert(action) { continue; }
+
+            let mut pressed = false;
+            let mut value = 0.0;
+            for (i, b) in self.bindings.iter().enumerate() {
+                if b.action != action { continue; }
+                if is_button(b.trigger) && self.binding_values[i] != 0.0 {
+                    pressed = true;
+                }
+                value += self.binding_values[i];
+            }
+
+            if pressed && !self.pressed.contains(action) {
+                self.pressed.insert(action.to_owned());
+                f(ActionEvent::Press(action.to_owned()));
+            } else if !pressed && self.pressed.contains(action) {
+                self.pressed.remove(action);
+                f(ActionEvent::Release(action.to_owned()));
+            }
+            let old_value = self.values.get(action).map(|&x| x).unwrap_or(0.0);
+            if value != old_value {
+                self.values.insert(action.to_owned(), value);
+                f(ActionEvent::Value(action.to_owned(), value));
+            }
+        }
+    }
+}
+
+// Returns true if the trigger is pressed and released.
+fn is_button(trigger: Trigger) -> bool {
+    match trigger {
+        Trigger::Button(_, _) | Trigger::Scancode(_, _) => true,
+        Trigger::ControllerAxis(_, _) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_map_buttons() {
+        use { Button, Input, Key };
+
+        let mut map = ActionMap::new(vec![
+            Binding::button("move_x", Key::A).scale(-1.0),
+            Binding::button("move_x", Key::D),
+            Binding::button("jump", Key::Space),
+        ]);
+        let mut events = vec![];
+        map.event(&Input::Press(Button::Keyboard(Key::D).into()), |e| events.push(e));
+        map.event(&Input::Press(Button::Keyboard(Key::A).into()), |e| events.push(e));
+        map.event(&Input::Release(Button::Keyboard(Key::D).into()), |e| events.push(e));
+        assert_eq!(events, vec![
+            ActionEvent::Press("move_x".into()),
+            ActionEvent::Value("move_x".into(), 1.0),
+            ActionEvent::Value("move_x".into(), 0.0),
+            ActionEvent::Value("move_x".into(), -1.0),
+        ]);
+        assert!(map.is_pressed("move_x"));
+        assert!(!map.is_pressed("jump"));
+
+        events.clear();
+        map.event(&Input::Focus(false), |e| events.push(e));
+        assert_eq!(events, vec![
+            ActionEvent::Release("move_x".into()),
+            ActionEvent::Value("move_x".into(), 0.0),
+        ]);
+    }
+
+    #[test]
+    fn test_action_map_modifiers() {
+        use { Button, Input, Key };
+        use keyboard::CTRL;
+
+        let mut map = ActionMap::new(vec![
+            Binding::button_with_modifiers("save", Key::S, CTRL),
+            Binding::button("back", Key::S),
+        ]);
+        let mut events = vec![];
+        map.event(&Input::Press(Button::Keyboard(Key::LCtrl).into()), |e| events.push(e));
+        map.event(&Input::Press(Button::Keyboard(Key::S).into()), |e| events.push(e));
+        assert!(map.is_pressed("save"));
+        assert!(!map.is_pressed("back"));
+        map.event(&Input::Release(Button::Keyboard(Key::S).into()), |e| events.push(e));
+        assert!(!map.is_pressed("save"));
+    }
+
+    #[test]
+    fn test_action_map_scancode() {
+        use { Button, ButtonArgs, Input, Key, Scancode };
+
+        let mut map = ActionMap::new(vec![
+            Binding::scancode("forward", Scancode::W),
+        ]);
+        let mut events = vec![];
+        // The key at the position of W on an AZERTY keyboard.
+        let args = ButtonArgs::new(Button::Keyboard(Key::Z)).scancode(Scancode::W);
+        map.event(&Input::Press(args), |e| events.push(e));
+        assert!(map.is_pressed("forward"));
+        map.event(&Input::Press(Button::Keyboard(Key::W).into()), |e| events.push(e));
+        map.event(&Input::Release(args), |e| events.push(e));
+        assert!(!map.is_pressed("forward"));
+    }
+
+    #[test]
+    fn test_action_map_axis() {
+        use { ControllerAxisArgs, ControllerConnection, Input, Motion };
+
+        let mut map = ActionMap::new(vec![
+            Binding::controller_axis("move_x", 0, 1).scale(2.0),
+        ]);
+        let mut events = vec![];
+        map.event(&Input::Move(Motion::ControllerAxis(
+            ControllerAxisArgs::new(0, 1, 0.5))), |e| events.push(e));
+        map.event(&Input::Move(Motion::ControllerAxis(
+            ControllerAxisArgs::new(1, 1, 0.5))), |e| events.push(e));
+        assert_eq!(events, vec![ActionEvent::Value("move_x".into(), 1.0)]);
+        assert_eq!(map.value("move_x"), 1.0);
+        assert!(!map.is_pressed("move_x"));
+
+        events.clear();
+        map.event(&Input::ControllerConnection(
+            ControllerConnection::Disconnected { id: 0 }), |e| events.push(e));
+        assert_eq!(events, vec![ActionEvent::Value("move_x".into(), 0.0)]);
+    }
+
+    #[test]
+    fn test_trigger_from_str() {
+        use { Button, ControllerButton, Key, MouseButton, Scancode };
+        use keyboard::CTRL_SHIFT;
+
+        let triggers = vec![
+            ("Ctrl+Shift+F5", Trigger::Button(Button::Keyboard(Key::F5), CTRL_SHIFT)),
+            ("Mouse Left", Trigger::Button(Button::Mouse(MouseButton::Left), NO_MODIFIER)),
+            ("Pad0 Button3", Trigger::Button(
+                Button::Controller(ControllerButton::new(0, 3)), NO_MODIFIER)),
+            ("Scancode W", Trigger::Scancode(Scancode::W, NO_MODIFIER)),
+            ("Pad1 Axis2", Trigger::ControllerAxis(1, 2)),
+        ];
+        for (text, trigger) in triggers {
+            assert_eq!(trigger.to_string(), text);
+            assert_eq!(text.parse(), Ok(trigger));
+        }
+        assert!("Ctrl+".parse::<Trigger>().is_err());
+        assert!("Pad0 Button".parse::<Trigger>().is_err());
+    }
+
+    #[test]
+    fn test_action_map_json() {
+        use { Key, MouseButton, Scancode };
+
+        let map = ActionMap::new(vec![
+            Binding::button("jump", Key::Space),
+            Binding::button("fire", MouseButton::Left),
+            Binding::scancode("forward", Scancode::W),
+            Binding::controller_axis("move_x", 0, 0),
+        ]);
+        let map2 = ActionMap::from_json(&map.to_json()).unwrap();
+        assert_eq!(map.bindings(), map2.bindings());
+    }
+}
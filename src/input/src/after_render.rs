@@ -1,6 +1,7 @@
 use std::any::Any;
 
-use GenericEvent;
+use { ArgsError, GenericEvent };
+use generic_event::try_with_args;
 use AFTER_RENDER;
 
 /// After render arguments.
@@ -18,6 +19,8 @@ pub trait AfterRenderEvent: Sized {
     fn after_render_args(&self) -> Option<AfterRenderArgs> {
         self.after_render(|args| args.clone())
     }
+    /// Returns after render arguments, or an error if they have an unexpected type.
+    fn try_after_render_args(&self) -> Result<Option<AfterRenderArgs>, ArgsError>;
 }
 
 impl<T: GenericEvent> AfterRenderEvent for T {
@@ -25,19 +28,15 @@ impl<T: GenericEvent> AfterRenderEvent for T {
         GenericEvent::from_args(AFTER_RENDER, args as &Any, old_event)
     }
 
-    fn after_render<U, F>(&self, mut f: F) -> Option<U>
+    fn after_render<U, F>(&self, f: F) -> Option<U>
         where F: FnMut(&AfterRenderArgs) -> U
     {
-        if self.event_id() != AFTER_RENDER {
-            return None;
-        }
-        self.with_args(|any| {
-            if let Some(args) = any.downcast_ref::<AfterRenderArgs>() {
-                Some(f(args))
-            } else {
-                panic!("Expected AfterRenderArgs")
-            }
-        })
+        try_with_args(self, AFTER_RENDER, "AfterRenderArgs", f)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_after_render_args(&self) -> Result<Option<AfterRenderArgs>, ArgsError> {
+        try_with_args(self, AFTER_RENDER, "AfterRenderArgs", |args: &AfterRenderArgs| args.clone())
     }
 }
 
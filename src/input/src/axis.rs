@@ -0,0 +1,334 @@
+//! Dead zones and response curves for controller axes.
+//!
+//! Cheap controllers report small positions when a stick is at rest.
+//! An `AxisFilter` maps positions inside a dead zone to zero,
+//! rescales the remaining range so it starts at zero
+//! and applies a response curve.
+
+use std::collections::HashMap;
+use std::default::Default;
+
+use { ControllerAxisArgs, GenericEvent };
+
+/// Maps a position in range 0..1 after the dead zone to an output in 0..1.
+#[derive(Copy, Clone, Debug)]
+pub enum ResponseCurve {
+    /// The output is the same as the input.
+    Linear,
+    /// The output is the input squared, for finer control near the center.
+    Quadratic,
+    /// The output is the input raised to a power.
+    Power(f64),
+    /// A custom function.
+    Custom(fn(f64) -> f64),
+}
+
+impl ResponseCurve {
+    /// Applies the curve to a value in range 0..1.
+    pub fn apply(&self, x: f64) -> f64 {
+        match *self {
+            ResponseCurve::Linear => x,
+            ResponseCurve::Quadratic => x * x,
+            ResponseCurve::Power(p) => x.powf(p),
+            ResponseCurve::Custom(f) => f(x),
+        }
+    }
+}
+
+/// Settings for filtering an axis or a stick.
+#[derive(Copy, Clone, Debug)]
+pub struct AxisSettings {
+    /// Positions with magnitude up to this value become zero.
+    pub dead_zone: f64,
+    /// Positions with magnitude from this value become 1.
+    pub saturation: f64,
+    /// The response curve applied after the dead zone and saturation.
+    pub curve: ResponseCurve,
+}
+
+impl AxisSettings {
+    /// Creates settings that do not change the position.
+    pub fn new() -> AxisSettings {
+        AxisSettings {
+            dead_zone: 0.0,
+            saturation: 1.0,
+            curve: ResponseCurve::Linear,
+        }
+    }
+
+    /// Sets the dead zone.
+    pub fn dead_zone(mut self, value: f64) -> AxisSettings {
+        self.dead_zone = value;
+        self
+    }
+
+    /// Sets the saturation.
+    pub fn saturation(mut self, value: f64) -> AxisSettings {
+        self.saturation = value;
+        self
+    }
+
+    /// Sets the response curve.
+    pub fn curve(mut self, value: ResponseCurve) -> AxisSettings {
+        self.curve = value;
+        self
+    }
+
+    /// Maps a magnitude to the output range 0..1.
+    pub fn apply_magnitude(&self, magnitude: f64) -> f64 {
+        if magnitude <= self.dead_zone { return 0.0; }
+        if magnitude >= self.saturation { return self.curve.apply(1.0); }
+        let x = (magnitude - self.dead_zone) / (self.saturation - self.dead_zone);
+        self.curve.apply(x)
+    }
+
+    /// Filters the position of a single axis.
+    pub fn apply(&self, position: f64) -> f64 {
+        let res = self.apply_magnitude(position.abs());
+        if position < 0.0 { -res } else { res }
+    }
+
+    /// Filters the position of a stick with a radial dead zone.
+    ///
+    /// The direction is kept, while the distance from the center is filtered.
+    pub fn apply_radial(&self, pos: [f64; 2]) -> [f64; 2] {
+        let magnitude = (pos[0] * pos[0] + pos[1] * pos[1]).sqrt();
+        if magnitude == 0.0 { return [0.0, 0.0]; }
+        let scale = self.apply_magnitude(magnitude) / magnitude;
+        [pos[0] * scale, pos[1] * scale]
+    }
+}
+
+impl Default for AxisSettings {
+    fn default() -> AxisSettings { AxisSettings::new() }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Stick {
+    x_axis: u8,
+    y_axis: u8,
+    settings: AxisSettings,
+}
+
+/// Filters controller axis positions.
+///
+/// Axes are identified by index, and the same settings are used
+/// for every controller.
+/// A pair of axes can be configured as a stick,
+/// which uses a radial dead zone.
+#[derive(Clone, Debug)]
+pub struct AxisFilter {
+    default: AxisSettings,
+    axes: HashMap<u8, AxisSettings>,
+    sticks: Vec<Stick>,
+    // The last unfiltered position of stick axes, per controller.
+    raw: HashMap<(i32, u8), f64>,
+}
+
+impl AxisFilter {
+    /// Creates a new axis filter.
+    ///
+    /// The settings are used for axes that are not configured.
+    pub fn new(default: AxisSettings) -> AxisFilter {
+        AxisFilter {
+            default: default,
+            axes: HashMap::new(),
+            sticks: vec![],
+            raw: HashMap::new(),
+        }
+    }
+
+    /// Sets the settings of an axis.
+    pub fn set_axis(&mut self, axis: u8, settings: AxisSettings) {
+        self.axes.insert(axis, settings);
+    }
+
+    /// Sets the settings of an axis.
+    ///
+    /// This method moves the current filter,
+    /// unlike `set_axis()`, so that it can be used in method chaining.
+    pub fn axis(mut self, axis: u8, settings: AxisSettings) -> AxisFilter {
+        self.set_axis(axis, settings);
+        self
+    }
+
+    /// Makes a pair of axes a stick with a radial dead zone.
+    ///
+    /// This takes priority over settings for each axis.
+    /// Sticks that use either axis are removed.
+    pub fn set_stick(&mut self, x_axis: u8, y_axis: u8, settings: AxisSettings) {
+        self.sticks.retain(|s| {
+            s.x_axis != x_axis && s.x_axis != y_axis &&
+            s.y_axis != x_axis && s.y_axis != y_axis
+        });
+        self.sticks.push(Stick {
+            x_axis: x_axis,
+            y_axis: y_axis,
+            settings: settings,
+        });
+    }
+
+    /// Makes a pair of axes a stick with a radial dead zone.
+    ///
+    /// This method moves the current filter,
+    /// unlike `set_stick()`, so that it can be used in method chaining.
+    pub fn stick(mut self, x_axis: u8, y_axis: u8, settings: AxisSettings) -> AxisFilter {
+        self.set_stick(x_axis, y_axis, settings);
+        self
+    }
+
+    /// Filters the position in controller axis arguments.
+    ///
+    /// The filtered position of a stick axis depends on both axes,
+    /// so a change of one axis also changes the other.
+    /// Use `stick_position()` to read both axes of a stick.
+    pub fn filter(&mut self, args: ControllerAxisArgs) -> ControllerAxisArgs {
+        let mut res = args;
+        for stick in &self.sticks {
+            let (other, is_x) = if args.axis == stick.x_axis {
+                (stick.y_axis, true)
+            } else if args.axis == stick.y_axis {
+                (stick.x_axis, false)
+            } else {
+                continue;
+            };
+            self.raw.insert((args.id, args.axis), args.position);
+            let other_pos = self.raw.get(&(args.id, other)).map(|&x| x).unwrap_or(0.0);
+            res.position = if is_x {
+                stick.settings.apply_radial([args.position, other_pos])[0]
+            } else {
+                stick.settings.apply_radial([other_pos, args.position])[1]
+            };
+            return res;
+        }
+        let settings = self.axes.get(&args.axis).unwrap_or(&self.default);
+        res.position = settings.apply(args.position);
+        res
+    }
+
+    /// Returns the filtered position of a stick of a controller.
+    ///
+    /// Returns `None` if the axes are not configured as a stick.
+    /// Axes that have not been moved are at the center.
+    pub fn stick_position(&self, id: i32, x_axis: u8, y_axis: u8) -> Option<[f64; 2]> {
+        let stick = match self.sticks.iter()
+            .find(|s| s.x_axis == x_axis && s.y_axis == y_axis) {
+            Some(stick) => stick,
+            None => return None,
+        };
+        let x = self.raw.get(&(id, x_axis)).map(|&x| x).unwrap_or(0.0);
+        let y = self.raw.get(&(id, y_axis)).map(|&x| x).unwrap_or(0.0);
+        Some(stick.settings.apply_radial([x, y]))
+    }
+
+    /// Returns a filtered event if this is a controller axis event.
+    ///
+    /// When a controller is disconnected, the stored stick positions are removed.
+    /// Events with arguments of an unexpected type are ignored.
+    pub fn event<E: GenericEvent>(&mut self, e: &E) -> Option<E> {
+        use { ControllerAxisEvent, ControllerConnection, ControllerConnectionEvent };
+
+        if let Ok(Some(ControllerConnection::Disconnected { id })) =
+            e.try_controller_connection_args() {
+            let keys: Vec<(i32, u8)> = self.raw.keys()
+                .filter(|&&(controller, _)| controller == id)
+                .map(|&key| key).collect();
+            for key in &keys {
+                self.raw.remove(key);
+            }
+        }
+
+        match e.try_controller_axis_args() {
+            Ok(Some(args)) => {
+                let args = self.filter(args);
+                ControllerAxisEvent::from_controller_axis_args(args, e)
+            }
+            _ => None
+        }
+    }
+}
+
+impl Default for AxisFilter {
+    fn default() -> AxisFilter { AxisFilter::new(Default::default()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_axis_settings() {
+        let settings = AxisSettings::new().dead_zone(0.2).saturation(0.6);
+        assert_eq!(settings.apply(0.1), 0.0);
+        assert_eq!(settings.apply(-0.2), 0.0);
+        assert!((settings.apply(0.4) - 0.5).abs() < 1e-6);
+        assert!((settings.apply(-0.4) + 0.5).abs() < 1e-6);
+        assert_eq!(settings.apply(0.9), 1.0);
+
+        let settings = settings.curve(ResponseCurve::Quadratic);
+        assert!((settings.apply(-0.4) + 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_axis_filter_stick() {
+        use ControllerAxisArgs;
+
+        let settings = AxisSettings::new().dead_zone(0.2);
+        let mut filter = AxisFilter::default().stick(0, 1, settings);
+        // Inside the radial dead zone, even if one axis is outside on its own.
+        filter.filter(ControllerAxisArgs::new(0, 1, 0.15));
+        let args = filter.filter(ControllerAxisArgs::new(0, 0, 0.1));
+        assert_eq!(args.position, 0.0);
+        let args = filter.filter(ControllerAxisArgs::new(0, 0, 0.6));
+        assert!(args.position > 0.0);
+        // Other controllers have their own state.
+        let args = filter.filter(ControllerAxisArgs::new(1, 0, 0.1));
+        assert_eq!(args.position, 0.0);
+        // Other axes are unchanged.
+        let args = filter.filter(ControllerAxisArgs::new(0, 2, 0.1));
+        assert_eq!(args.position, 0.1);
+    }
+
+    #[test]
+    fn test_axis_filter_stick_position() {
+        use ControllerAxisArgs;
+
+        let settings = AxisSettings::new().dead_zone(0.2);
+        let mut filter = AxisFilter::default().stick(0, 1, settings);
+        assert_eq!(filter.stick_position(0, 0, 1), Some([0.0, 0.0]));
+        let args = filter.filter(ControllerAxisArgs::new(0, 0, 0.5));
+        assert!((args.position - 0.375).abs() < 1e-6);
+        // Moving the other axis changes the filtered position of both.
+        filter.filter(ControllerAxisArgs::new(0, 1, 0.8));
+        let pos = filter.stick_position(0, 0, 1).unwrap();
+        assert!((pos[0] - 0.4926).abs() < 1e-3);
+        assert_eq!(filter.stick_position(1, 0, 1), Some([0.0, 0.0]));
+        assert_eq!(filter.stick_position(0, 1, 2), None);
+    }
+
+    #[test]
+    fn test_axis_filter_replace_stick() {
+        use ControllerAxisArgs;
+
+        let mut filter = AxisFilter::default()
+            .stick(0, 1, AxisSettings::new().dead_zone(0.2))
+            .stick(1, 2, AxisSettings::new());
+        let args = filter.filter(ControllerAxisArgs::new(0, 1, 0.1));
+        assert_eq!(args.position, 0.1);
+        // The first stick was removed, so axis 0 uses the default settings.
+        let args = filter.filter(ControllerAxisArgs::new(0, 0, 0.1));
+        assert_eq!(args.position, 0.1);
+    }
+
+    #[test]
+    fn test_axis_filter_event() {
+        use { ControllerAxisArgs, Input, Motion };
+
+        let mut filter = AxisFilter::new(AxisSettings::new().dead_zone(0.2));
+        let e = Input::Move(Motion::ControllerAxis(
+            ControllerAxisArgs::new(0, 0, 0.1)));
+        assert_eq!(filter.event(&e), Some(Input::Move(Motion::ControllerAxis(
+            ControllerAxisArgs::new(0, 0, 0.0)))));
+        assert_eq!(filter.event(&Input::Focus(true)), None);
+    }
+}
@@ -0,0 +1,173 @@
+//! Counts mouse clicks.
+//!
+//! Positions are in window coordinates.
+//! Time is measured by summing the delta time of update events.
+
+use std::default::Default;
+
+use { Button, GenericEvent, MouseButton };
+
+/// A click with a mouse button.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Click {
+    /// The mouse button.
+    pub button: MouseButton,
+    /// The cursor position when the button was pressed.
+    pub pos: [f64; 2],
+    /// The number of clicks in a row, 1 for single and 2 for double click.
+    pub count: u32,
+}
+
+/// Thresholds used to count clicks.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ClickSettings {
+    /// How far the cursor can move and still count as the same click.
+    pub distance: f64,
+    /// The maximum time between two presses of a multiple click in seconds.
+    pub time: f64,
+}
+
+impl Default for ClickSettings {
+    fn default() -> ClickSettings {
+        ClickSettings {
+            distance: 4.0,
+            time: 0.5,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Press {
+    button: MouseButton,
+    pos: [f64; 2],
+    time: f64,
+    count: u32,
+}
+
+/// Turns mouse button presses and releases into clicks.
+///
+/// A click is emitted when the button is released,
+/// unless the cursor moved too far since the press.
+/// A press shortly after and close to the previous click
+/// continues the count of that click.
+#[derive(Clone, Debug)]
+pub struct ClickTracker {
+    /// The thresholds used to count clicks.
+    pub settings: ClickSettings,
+    time: f64,
+    cursor: [f64; 2],
+    pressed: Vec<Press>,
+    last_click: Option<Press>,
+}
+
+impl ClickTracker {
+    /// Creates a new click tracker.
+    pub fn new(settings: ClickSettings) -> ClickTracker {
+        ClickTracker {
+            settings: settings,
+            time: 0.0,
+            cursor: [0.0; 2],
+            pressed: vec![],
+            last_click: None,
+        }
+    }
+
+    /// Change click state depending on event.
+    ///
+    /// Calls closure for every click.
+    /// When the window loses focus, pressed buttons and click counts are reset.
+    /// Events with arguments of an unexpected type are ignored.
+    pub fn event<E, F>(&mut self, e: &E, mut f: F)
+        where E: GenericEvent, F: FnMut(Click)
+    {
+        use { FocusEvent, MouseCursorEvent, PressEvent, ReleaseEvent, UpdateEvent };
+
+        if let Ok(Some(args)) = e.try_mouse_cursor_full_args() {
+            self.cursor = [args.x, args.y];
+        }
+        let press = e.try_press_button_args().unwrap_or(None).map(|args| args.button);
+        if let Some(Button::Mouse(button)) = press {
+            let count = match self.last_click {
+                Some(last) if last.button == button &&
+                    self.time - last.time <= self.settings.time &&
+                    distance(last.pos, self.cursor) <= self.settings.distance
+                    => last.count + 1,
+                _ => 1
+            };
+            self.pressed.retain(|p| p.button != button);
+            self.pressed.push(Press {
+                button: button,
+                pos: self.cursor,
+                time: self.time,
+                count: count,
+            });
+        }
+        let release = e.try_release_button_args().unwrap_or(None).map(|args| args.button);
+        if let Some(Button::Mouse(button)) = release {
+            if let Some(i) = self.pressed.iter().position(|p| p.button == button) {
+                let press = self.pressed.remove(i);
+                if distance(press.pos, self.cursor) <= self.settings.distance {
+                    self.last_click = Some(press);
+                    f(Click {
+                        button: button,
+                        pos: press.pos,
+                        count: press.count,
+                    });
+                } else {
+                    self.last_click = None;
+                }
+            }
+        }
+        if let Ok(Some(args)) = e.try_update_args() {
+            self.time += args.dt;
+        }
+        if let Ok(Some(false)) = e.try_focus_args() {
+            self.pressed.clear();
+            self.last_click = None;
+        }
+    }
+}
+
+impl Default for ClickTracker {
+    fn default() -> ClickTracker { ClickTracker::new(Default::default()) }
+}
+
+fn distance(a: [f64; 2], b: [f64; 2]) -> f64 {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    (dx * dx + dy * dy).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_click_count() {
+        use { Button, Event, Input, Motion, MouseButton, UpdateArgs };
+
+        let left = Button::Mouse(MouseButton::Left);
+        let press: Event = Event::Input(Input::Press(left.into()));
+        let release: Event = Event::Input(Input::Release(left.into()));
+        let update = |dt: f64| -> Event { Event::Update(UpdateArgs { dt: dt }) };
+        let cursor = |x: f64| -> Event {
+            Event::Input(Input::Move(Motion::MouseCursor((x, 10.0).into())))
+        };
+        let mut tracker = ClickTracker::default();
+        let mut counts = vec![];
+        tracker.event(&cursor(10.0), |c| counts.push(c.count));
+        for _ in 0..3 {
+            tracker.event(&press, |c| counts.push(c.count));
+            tracker.event(&release, |c| counts.push(c.count));
+            tracker.event(&update(0.1), |c| counts.push(c.count));
+        }
+        // Too late for a fourth click in a row.
+        tracker.event(&update(1.0), |c| counts.push(c.count));
+        tracker.event(&press, |c| counts.push(c.count));
+        tracker.event(&release, |c| counts.push(c.count));
+        // Moving away between press and release is not a click.
+        tracker.event(&press, |c| counts.push(c.count));
+        tracker.event(&cursor(50.0), |c| counts.push(c.count));
+        tracker.event(&release, |c| counts.push(c.count));
+        assert_eq!(counts, vec![1, 2, 3, 1]);
+    }
+}
@@ -0,0 +1,91 @@
+//! Text composition with an input method editor (IME).
+//!
+//! Input methods for Chinese, Japanese and Korean compose text
+//! in several steps before committing it.
+//! While composing, the in-progress text (the preedit string)
+//! should be drawn at the text cursor.
+//! The committed text is received as a text event.
+
+use std::any::Any;
+
+use { ArgsError, GenericEvent, COMPOSITION };
+use generic_event::try_with_args;
+
+/// A change in the composition of text.
+#[derive(Clone, RustcDecodable, RustcEncodable, PartialEq, Eq, Debug)]
+pub enum Composition {
+    /// The user started composing text.
+    Start,
+    /// The preedit string changed.
+    Update {
+        /// The text being composed.
+        text: String,
+        /// The selected range within the text, in bytes.
+        ///
+        /// When start and end are equal, this is the position of the cursor.
+        selection: (usize, usize),
+    },
+    /// The composition ended, either committed or cancelled.
+    End,
+}
+
+/// Composition of text with an input method editor.
+pub trait CompositionEvent: Sized {
+    /// Creates a composition event.
+    fn from_composition(args: &Composition, old_event: &Self) -> Option<Self>;
+    /// Calls closure if this is a composition event.
+    fn composition<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(&Composition) -> U;
+    /// Returns composition arguments.
+    fn composition_args(&self) -> Option<Composition> {
+        self.composition(|args| args.clone())
+    }
+    /// Returns composition arguments, or an error if they have an unexpected type.
+    fn try_composition_args(&self) -> Result<Option<Composition>, ArgsError>;
+}
+
+impl<T: GenericEvent> CompositionEvent for T {
+    fn from_composition(args: &Composition, old_event: &Self) -> Option<Self> {
+        GenericEvent::from_args(COMPOSITION, args as &Any, old_event)
+    }
+
+    fn composition<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(&Composition) -> U
+    {
+        try_with_args(self, COMPOSITION, "Composition", f)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_composition_args(&self) -> Result<Option<Composition>, ArgsError> {
+        try_with_args(self, COMPOSITION, "Composition", |args: &Composition| args.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_composition() {
+        use super::super::Input;
+
+        let e = Input::Composition(Composition::Start);
+        let args = Composition::Update { text: "にほん".to_owned(), selection: (3, 6) };
+        let x: Option<Input> = CompositionEvent::from_composition(&args, &e);
+        let y: Option<Input> = x.clone().unwrap().composition(|args|
+            CompositionEvent::from_composition(args, x.as_ref().unwrap())).unwrap();
+        assert_eq!(x, y);
+    }
+
+    #[test]
+    fn test_event_composition() {
+        use Event;
+        use super::super::Input;
+
+        let e = Event::Input(Input::Composition(Composition::Start));
+        let x: Option<Event> = CompositionEvent::from_composition(&Composition::End, &e);
+        let y: Option<Event> = x.clone().unwrap().composition(|args|
+            CompositionEvent::from_composition(args, x.as_ref().unwrap())).unwrap();
+        assert_eq!(x, y);
+    }
+}
@@ -2,8 +2,11 @@
 //! Back-end agnostic controller events.
 
 use std::any::Any;
+use std::fmt;
+use std::str::FromStr;
 
-use { GenericEvent, CONTROLLER_AXIS };
+use { ArgsError, GenericEvent, CONTROLLER_AXIS, CONTROLLER_CONNECTION };
+use generic_event::try_with_args;
 
 /// Components of a controller button event. Not guaranteed consistent across
 /// backends.
@@ -26,6 +29,32 @@ impl ControllerButton {
     }
 }
 
+/// Formats the controller button as `Pad<id> Button<button>`,
+/// for example `Pad0 Button3`.
+impl fmt::Display for ControllerButton {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Pad{} Button{}", self.id, self.button)
+    }
+}
+
+impl FromStr for ControllerButton {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ControllerButton, String> {
+        let err = || format!("Expected `Pad<id> Button<button>`, found `{}`", s);
+        let mut parts = s.split(' ');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(pad), Some(button), None)
+                if pad.starts_with("Pad") && button.starts_with("Button") => {
+                let id = try!(pad[3..].parse().map_err(|_| err()));
+                let button = try!(button[6..].parse().map_err(|_| err()));
+                Ok(ControllerButton::new(id, button))
+            }
+            _ => Err(err())
+        }
+    }
+}
+
 /// Components of a controller axis move event. Not guaranteed consistent across
 /// backends.
 #[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq, Debug)]
@@ -51,6 +80,40 @@ impl ControllerAxisArgs {
     }
 }
 
+/// A controller was connected or disconnected.
+#[derive(Clone, RustcDecodable, RustcEncodable, PartialEq, Debug)]
+pub enum ControllerConnection {
+    /// A controller was connected.
+    Connected {
+        /// Which controller, the same as in button and axis events.
+        id: i32,
+        /// The name of the controller.
+        name: String,
+        /// The device GUID as a hexadecimal string, if reported by the backend.
+        ///
+        /// This is the key of the SDL GameControllerDB format,
+        /// see `GamepadDb::find`.
+        guid: Option<String>,
+    },
+    /// A controller was disconnected.
+    ///
+    /// The id might be reused when another controller is connected.
+    Disconnected {
+        /// Which controller.
+        id: i32,
+    },
+}
+
+impl ControllerConnection {
+    /// Returns which controller was connected or disconnected.
+    pub fn id(&self) -> i32 {
+        match *self {
+            ControllerConnection::Connected { id, .. } => id,
+            ControllerConnection::Disconnected { id } => id,
+        }
+    }
+}
+
 /// The position of a controller axis changed.
 pub trait ControllerAxisEvent: Sized {
     /// Creates a controller axis event.
@@ -65,6 +128,8 @@ pub trait ControllerAxisEvent: Sized {
     fn controller_axis_args(&self) -> Option<ControllerAxisArgs> {
         self.controller_axis(|args| args)
     }
+    /// Returns controller axis arguments, or an error if they have an unexpected type.
+    fn try_controller_axis_args(&self) -> Result<Option<ControllerAxisArgs>, ArgsError>;
 }
 
 impl<T: GenericEvent> ControllerAxisEvent for T {
@@ -78,16 +143,53 @@ impl<T: GenericEvent> ControllerAxisEvent for T {
     fn controller_axis<U, F>(&self, mut f: F) -> Option<U>
         where F: FnMut(ControllerAxisArgs) -> U
     {
-        if self.event_id() != CONTROLLER_AXIS {
-            return None;
-        }
-        self.with_args(|any| {
-            if let Some(&args) = any.downcast_ref::<ControllerAxisArgs>() {
-                Some(f(args))
-            } else {
-                panic!("Expected ControllerAxisArgs")
-            }
-        })
+        try_with_args(self, CONTROLLER_AXIS, "ControllerAxisArgs",
+                      |&args: &ControllerAxisArgs| f(args))
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_controller_axis_args(&self) -> Result<Option<ControllerAxisArgs>, ArgsError> {
+        try_with_args(self, CONTROLLER_AXIS, "ControllerAxisArgs",
+                      |&args: &ControllerAxisArgs| args)
+    }
+}
+
+/// A controller was connected or disconnected.
+pub trait ControllerConnectionEvent: Sized {
+    /// Creates a controller connection event.
+    fn from_controller_connection(
+        args: &ControllerConnection,
+        old_event: &Self
+    ) -> Option<Self>;
+    /// Calls closure if this is a controller connection event.
+    fn controller_connection<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(&ControllerConnection) -> U;
+    /// Returns controller connection arguments.
+    fn controller_connection_args(&self) -> Option<ControllerConnection> {
+        self.controller_connection(|args| args.clone())
+    }
+    /// Returns controller connection arguments, or an error if they have an unexpected type.
+    fn try_controller_connection_args(&self) -> Result<Option<ControllerConnection>, ArgsError>;
+}
+
+impl<T: GenericEvent> ControllerConnectionEvent for T {
+    fn from_controller_connection(
+        args: &ControllerConnection,
+        old_event: &Self
+    ) -> Option<Self> {
+        GenericEvent::from_args(CONTROLLER_CONNECTION, args as &Any, old_event)
+    }
+
+    fn controller_connection<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(&ControllerConnection) -> U
+    {
+        try_with_args(self, CONTROLLER_CONNECTION, "ControllerConnection", f)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_controller_connection_args(&self) -> Result<Option<ControllerConnection>, ArgsError> {
+        try_with_args(self, CONTROLLER_CONNECTION, "ControllerConnection",
+                      |args: &ControllerConnection| args.clone())
     }
 }
 
@@ -126,3 +228,45 @@ mod controller_axis_tests {
         assert_eq!(a, b);
     }
 }
+
+#[cfg(test)]
+mod controller_connection_tests {
+    use super::*;
+
+    #[test]
+    fn test_input_controller_connection() {
+        use super::super::Input;
+
+        let e = Input::ControllerConnection(
+            ControllerConnection::Disconnected { id: 0 });
+        let a: Option<Input> = ControllerConnectionEvent::from_controller_connection(
+            &ControllerConnection::Connected {
+                id: 1,
+                name: "Pad".into(),
+                guid: Some("030000005e0400008e02000014010000".into()),
+            }, &e);
+        let b: Option<Input> = a.clone().unwrap().controller_connection(|args|
+            ControllerConnectionEvent::from_controller_connection(
+                args, a.as_ref().unwrap())).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_event_controller_connection() {
+        use Event;
+        use super::super::Input;
+
+        let e = Event::Input(Input::ControllerConnection(
+            ControllerConnection::Disconnected { id: 0 }));
+        let a: Option<Event> = ControllerConnectionEvent::from_controller_connection(
+            &ControllerConnection::Connected {
+                id: 1,
+                name: "Pad".into(),
+                guid: Some("030000005e0400008e02000014010000".into()),
+            }, &e);
+        let b: Option<Event> = a.clone().unwrap().controller_connection(|args|
+            ControllerConnectionEvent::from_controller_connection(
+                args, a.as_ref().unwrap())).unwrap();
+        assert_eq!(a, b);
+    }
+}
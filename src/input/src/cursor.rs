@@ -1,6 +1,7 @@
 use std::any::Any;
 
-use { GenericEvent, CURSOR };
+use { ArgsError, GenericEvent, CURSOR };
+use generic_event::try_with_args;
 
 /// When window gets or loses cursor
 pub trait CursorEvent: Sized {
@@ -13,6 +14,8 @@ pub trait CursorEvent: Sized {
     fn cursor_args(&self) -> Option<bool> {
         self.cursor(|val| val)
     }
+    /// Returns cursor arguments, or an error if they have an unexpected type.
+    fn try_cursor_args(&self) -> Result<Option<bool>, ArgsError>;
 }
 
 impl<T: GenericEvent> CursorEvent for T {
@@ -23,16 +26,12 @@ impl<T: GenericEvent> CursorEvent for T {
     fn cursor<U, F>(&self, mut f: F) -> Option<U>
         where F: FnMut(bool) -> U
     {
-        if self.event_id() != CURSOR {
-            return None;
-        }
-        self.with_args(|any| {
-            if let Some(&cursor) = any.downcast_ref::<bool>() {
-                Some(f(cursor))
-            } else {
-                panic!("Expected bool")
-            }
-        })
+        try_with_args(self, CURSOR, "bool", |&cursor: &bool| f(cursor))
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_cursor_args(&self) -> Result<Option<bool>, ArgsError> {
+        try_with_args(self, CURSOR, "bool", |&cursor: &bool| cursor)
     }
 }
 
@@ -0,0 +1,187 @@
+//! Custom events defined by libraries.
+//!
+//! A library defines its own event id, for example
+//! `EventId("mylib/score")`, and a payload type.
+//! Custom events flow through `Event::Custom`,
+//! and the payload is passed to `GenericEvent::with_args` and `GenericEvent::from_args`
+//! wrapped in `CustomArgs`.
+//!
+//! To provide a trait like `RenderEvent` for the payload,
+//! implement it for all `GenericEvent` types using `CustomEvent`.
+
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use { ArgsError, EventId, GenericEvent };
+
+/// The payload of a custom event.
+///
+/// The payload is shared when the event is cloned.
+#[derive(Clone)]
+pub struct CustomArgs(Arc<Any + Send + Sync>);
+
+impl CustomArgs {
+    /// Creates a custom payload.
+    pub fn new<T: Any + Send + Sync>(args: T) -> CustomArgs {
+        CustomArgs(Arc::new(args))
+    }
+
+    /// Returns the payload as `Any`.
+    pub fn as_any(&self) -> &Any {
+        &*self.0
+    }
+
+    /// Returns the payload if it has type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.as_any().downcast_ref::<T>()
+    }
+}
+
+impl fmt::Debug for CustomArgs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CustomArgs(..)")
+    }
+}
+
+/// Custom payloads are equal when they are shared by clones of the same event.
+impl PartialEq for CustomArgs {
+    fn eq(&self, other: &CustomArgs) -> bool {
+        self.as_any() as *const Any as *const () ==
+        other.as_any() as *const Any as *const ()
+    }
+}
+
+/// Creates and reads custom events.
+pub trait CustomEvent: Sized {
+    /// Creates a custom event.
+    ///
+    /// Returns `None` if the event type does not support custom events.
+    fn from_custom_args<T>(event_id: EventId, args: T, old_event: &Self) -> Option<Self>
+        where T: Any + Send + Sync;
+    /// Calls closure if this is a custom event with the id.
+    ///
+    /// Panics if the payload does not have type `T`.
+    fn custom<T, U, F>(&self, event_id: EventId, f: F) -> Option<U>
+        where T: Any, F: FnMut(&T) -> U;
+    /// Returns custom arguments.
+    fn custom_args<T>(&self, event_id: EventId) -> Option<T>
+        where T: Any + Clone
+    {
+        self.custom(event_id, |args: &T| args.clone())
+    }
+    /// Returns custom arguments, or an error if they do not have type `T`.
+    fn try_custom_args<T>(&self, event_id: EventId) -> Result<Option<T>, ArgsError>
+        where T: Any + Clone;
+}
+
+impl<E: GenericEvent> CustomEvent for E {
+    fn from_custom_args<T>(event_id: EventId, args: T, old_event: &Self) -> Option<Self>
+        where T: Any + Send + Sync
+    {
+        GenericEvent::from_args(event_id, &CustomArgs::new(args) as &Any, old_event)
+    }
+
+    fn custom<T, U, F>(&self, event_id: EventId, f: F) -> Option<U>
+        where T: Any, F: FnMut(&T) -> U
+    {
+        try_with_custom_args(self, event_id, f).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_custom_args<T>(&self, event_id: EventId) -> Result<Option<T>, ArgsError>
+        where T: Any + Clone
+    {
+        try_with_custom_args(self, event_id, |args: &T| args.clone())
+    }
+}
+
+// Calls closure with the payload of a custom event, if it has the id.
+//
+// The payload is unwrapped from `CustomArgs`,
+// and is also accepted unwrapped for events that pass it directly.
+fn try_with_custom_args<E, T, U, F>(e: &E, event_id: EventId, mut f: F)
+    -> Result<Option<U>, ArgsError>
+    where E: GenericEvent, T: Any, F: FnMut(&T) -> U
+{
+    if e.event_id() != event_id {
+        return Ok(None);
+    }
+    e.with_args(|any| {
+        let any = match any.downcast_ref::<CustomArgs>() {
+            Some(args) => args.as_any(),
+            None => any,
+        };
+        if let Some(args) = any.downcast_ref::<T>() {
+            Ok(Some(f(args)))
+        } else {
+            Err(ArgsError::new(event_id, "custom payload"))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use { EventId, GenericEvent };
+
+    const SCORE: EventId = EventId("test/score");
+
+    #[derive(Clone, PartialEq, Debug)]
+    struct ScoreArgs {
+        player: u32,
+        points: i32,
+    }
+
+    // A trait for the custom event, like the built-in event traits.
+    trait ScoreEvent: Sized {
+        fn from_score_args(args: &ScoreArgs, old_event: &Self) -> Option<Self>;
+        fn score<U, F>(&self, f: F) -> Option<U>
+            where F: FnMut(&ScoreArgs) -> U;
+    }
+
+    impl<T: GenericEvent> ScoreEvent for T {
+        fn from_score_args(args: &ScoreArgs, old_event: &Self) -> Option<Self> {
+            CustomEvent::from_custom_args(SCORE, args.clone(), old_event)
+        }
+
+        fn score<U, F>(&self, f: F) -> Option<U>
+            where F: FnMut(&ScoreArgs) -> U
+        {
+            self.custom(SCORE, f)
+        }
+    }
+
+    #[test]
+    fn test_event_custom() {
+        use { Event, UpdateArgs, UpdateEvent };
+
+        let args = ScoreArgs { player: 1, points: 10 };
+        let e: Event = Event::Update(UpdateArgs { dt: 0.0 });
+        let x: Option<Event> = ScoreEvent::from_score_args(&args, &e);
+        let x = x.unwrap();
+        assert_eq!(x.event_id(), SCORE);
+        assert_eq!(x.score(|args| args.clone()), Some(args.clone()));
+        assert_eq!(x.clone(), x);
+        assert_eq!(x.update_args(), None);
+        assert_eq!(e.score(|args| args.points), None);
+        assert_eq!(x.try_custom_args::<ScoreArgs>(SCORE), Ok(Some(args)));
+        assert!(x.try_custom_args::<u32>(SCORE).is_err());
+    }
+
+    #[test]
+    fn test_event_custom_forward() {
+        use { Event, Input, UpdateArgs };
+
+        // Forwarding an event through `with_args` and `from_args` keeps custom events.
+        let args = ScoreArgs { player: 1, points: 10 };
+        let e: Event = Event::Update(UpdateArgs { dt: 0.0 });
+        let x: Event = ScoreEvent::from_score_args(&args, &e).unwrap();
+        let y: Option<Event> = x.with_args(|any| GenericEvent::from_args(x.event_id(), any, &e));
+        assert_eq!(y, Some(x.clone()));
+        assert_eq!(y.unwrap().score(|args| args.clone()), Some(args));
+        // Input does not support custom events.
+        let old = Input::Focus(true);
+        let z: Option<Input> = x.with_args(|any| GenericEvent::from_args(x.event_id(), any, &old));
+        assert_eq!(z, None);
+    }
+}
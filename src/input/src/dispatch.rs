@@ -0,0 +1,605 @@
+//! Routes input events to a tree of nodes.
+//!
+//! A GUI library adds a node for each widget, with a rectangle in window coordinates.
+//! The dispatcher decides which nodes receive an event and in which order,
+//! and calls a closure for each of them.
+//!
+//! - Mouse, pen and touch events go to the deepest node under the pointer
+//! - After a mouse button is pressed, mouse events go to the same node
+//!   until all mouse buttons are released
+//! - The events of a touch go to the node where the touch started, until it ends
+//! - Keyboard, text and IME composition events go to the focused node
+//! - Tab and Shift+Tab move focus between focusable nodes, unless handled
+//! - Pressing a mouse button or starting a touch focuses the nearest focusable node
+//!   under the pointer
+//!
+//! Touch positions are relative to the window size,
+//! which the dispatcher reads from resize events or `set_window_size`.
+//!
+//! An event first travels from the root down towards the target (capture),
+//! then reaches the target, and then travels back up to the root (bubble).
+//! The closure returns `true` when it handles the event, which stops the routing.
+//! Other events are not routed.
+
+use { Button, GenericEvent, Key, MouseButton, Touch };
+use keyboard::{ ModifierKey, NO_MODIFIER, SHIFT };
+
+/// Identifies a node in a dispatcher.
+///
+/// The ids of removed nodes are not reused,
+/// even though their storage is.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct NodeId(usize, u32);
+
+/// The phase of routing an event.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DispatchPhase {
+    /// The event travels from the root towards the target.
+    Capture,
+    /// The event reached the target.
+    Target,
+    /// The event travels from the target back to the root.
+    Bubble,
+}
+
+// Storage for a node, reused after the node is removed.
+#[derive(Clone, Debug)]
+struct Slot {
+    // Incremented when the node is removed, so old ids do not match.
+    generation: u32,
+    node: Option<Node>,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    rect: [f64; 4],
+    focusable: bool,
+}
+
+/// Routes input events to a tree of nodes.
+///
+/// Later siblings are on top of earlier ones when hit testing.
+/// A node is only hit where its parent is hit too.
+/// Focus moves between focusable nodes in depth-first order.
+#[derive(Clone, Debug)]
+pub struct Dispatcher {
+    nodes: Vec<Slot>,
+    // The indices of empty slots.
+    free: Vec<usize>,
+    roots: Vec<NodeId>,
+    focus: Option<NodeId>,
+    modifiers: ModifierKey,
+    cursor: [f64; 2],
+    window_size: [f64; 2],
+    // The node receiving mouse events until all mouse buttons are released.
+    capture: Option<NodeId>,
+    // The mouse buttons held down while the pointer is captured.
+    captured_buttons: Vec<MouseButton>,
+    // The node receiving the events of each touch, by device and touch id.
+    touches: Vec<((i64, i64), NodeId)>,
+}
+
+impl Dispatcher {
+    /// Creates a new dispatcher without nodes.
+    pub fn new() -> Dispatcher {
+        Dispatcher {
+            nodes: vec![],
+            free: vec![],
+            roots: vec![],
+            focus: None,
+            modifiers: NO_MODIFIER,
+            cursor: [0.0; 2],
+            window_size: [0.0; 2],
+            capture: None,
+            captured_buttons: vec![],
+            touches: vec![],
+        }
+    }
+
+    /// Adds a node with a rectangle `[x, y, width, height]` in window coordinates.
+    ///
+    /// A node without parent is a root.
+    /// Panics if the parent is not in the dispatcher.
+    pub fn add(&mut self, parent: Option<NodeId>, rect: [f64; 4]) -> NodeId {
+        let id = match self.free.last() {
+            Some(&index) => NodeId(index, self.nodes[index].generation),
+            None => NodeId(self.nodes.len(), 0),
+        };
+        match parent {
+            Some(parent) => self.node_mut(parent).children.push(id),
+            None => self.roots.push(id),
+        }
+        let node = Node {
+            parent: parent,
+            children: vec![],
+            rect: rect,
+            focusable: false,
+        };
+        if self.free.pop().is_some() {
+            self.nodes[id.0].node = Some(node);
+        } else {
+            self.nodes.push(Slot { generation: 0, node: Some(node) });
+        }
+        id
+    }
+
+    /// Removes a node and its children.
+    ///
+    /// If the focused node is removed, no node has focus.
+    /// Returns `false` if the node is not in the dispatcher.
+    pub fn remove(&mut self, id: NodeId) -> bool {
+        let parent = match self.get(id) {
+            Some(node) => node.parent,
+            None => return false,
+        };
+        match parent {
+            Some(parent) => self.node_mut(parent).children.retain(|&x| x != id),
+            None => self.roots.retain(|&x| x != id),
+        }
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            if self.focus == Some(id) { self.focus = None; }
+            if self.capture == Some(id) {
+                self.capture = None;
+                self.captured_buttons.clear();
+            }
+            self.touches.retain(|&(_, target)| target != id);
+            let slot = &mut self.nodes[id.0];
+            if let Some(node) = slot.node.take() {
+                slot.generation = slot.generation.wrapping_add(1);
+                self.free.push(id.0);
+                stack.extend(node.children);
+            }
+        }
+        true
+    }
+
+    /// Returns true if the dispatcher has the node.
+    pub fn has_node(&self, id: NodeId) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Returns the rectangle of a node, if it is in the dispatcher.
+    pub fn rect(&self, id: NodeId) -> Option<[f64; 4]> {
+        self.get(id).map(|node| node.rect)
+    }
+
+    /// Sets the rectangle of a node.
+    ///
+    /// Returns `false` if the node is not in the dispatcher.
+    pub fn set_rect(&mut self, id: NodeId, rect: [f64; 4]) -> bool {
+        match self.get_mut(id) {
+            Some(node) => { node.rect = rect; true }
+            None => false,
+        }
+    }
+
+    /// Sets whether a node can have focus.
+    ///
+    /// Returns `false` if the node is not in the dispatcher.
+    pub fn set_focusable(&mut self, id: NodeId, focusable: bool) -> bool {
+        match self.get_mut(id) {
+            Some(node) => node.focusable = focusable,
+            None => return false,
+        }
+        if !focusable && self.focus == Some(id) {
+            self.focus = None;
+        }
+        true
+    }
+
+    /// Sets the window size, used to place touch events.
+    ///
+    /// The window size is also updated by resize events.
+    pub fn set_window_size(&mut self, size: [f64; 2]) {
+        self.window_size = size;
+    }
+
+    /// Returns the focused node.
+    pub fn focus(&self) -> Option<NodeId> {
+        self.focus
+    }
+
+    /// Sets the focused node.
+    ///
+    /// Returns `false` and keeps the focus if the node is not in the dispatcher,
+    /// or is not focusable.
+    pub fn set_focus(&mut self, id: Option<NodeId>) -> bool {
+        if let Some(id) = id {
+            if !self.get(id).map_or(false, |node| node.focusable) { return false; }
+        }
+        self.focus = id;
+        true
+    }
+
+    /// Moves focus to the next focusable node, wrapping around.
+    pub fn focus_next(&mut self) {
+        let order = self.focus_order();
+        if order.is_empty() { return; }
+        let next = match self.focus.and_then(|id| order.iter().position(|&x| x == id)) {
+            Some(i) => (i + 1) % order.len(),
+            None => 0,
+        };
+        self.focus = Some(order[next]);
+    }
+
+    /// Moves focus to the previous focusable node, wrapping around.
+    pub fn focus_prev(&mut self) {
+        let order = self.focus_order();
+        if order.is_empty() { return; }
+        let prev = match self.focus.and_then(|id| order.iter().position(|&x| x == id)) {
+            Some(i) => (i + order.len() - 1) % order.len(),
+            None => order.len() - 1,
+        };
+        self.focus = Some(order[prev]);
+    }
+
+    /// Returns the deepest node at a position in window coordinates.
+    pub fn hit_test(&self, pos: [f64; 2]) -> Option<NodeId> {
+        let mut hit = None;
+        let mut candidates = &self.roots;
+        loop {
+            match candidates.iter().rev().find(|&&id| inside(self.node(id).rect, pos)) {
+                Some(&id) => {
+                    hit = Some(id);
+                    candidates = &self.node(id).children;
+                }
+                None => return hit,
+            }
+        }
+    }
+
+    /// Routes an event to nodes.
+    ///
+    /// Calls closure for each node on the route with the phase,
+    /// until the closure returns `true`.
+    /// Events with arguments of an unexpected type are ignored.
+    pub fn event<E, F>(&mut self, e: &E, mut f: F)
+        where E: GenericEvent, F: FnMut(NodeId, DispatchPhase, &E) -> bool
+    {
+        use { CompositionEvent, MouseCursorEvent, MouseScrollEvent, PenEvent, PressEvent,
+              ReleaseEvent, ResizeEvent, TextEvent, TouchEvent };
+
+        self.modifiers.event(e);
+        if let Ok(Some(args)) = e.try_mouse_cursor_full_args() {
+            self.cursor = [args.x, args.y];
+        }
+        if let Ok(Some(size)) = e.try_resize_args() {
+            self.window_size = [size[0] as f64, size[1] as f64];
+        }
+
+        if let Ok(Some(args)) = e.try_touch_args() {
+            let touch = (args.device, args.id);
+            let target = match args.touch {
+                Touch::Start => {
+                    let pos = [args.x * self.window_size[0], args.y * self.window_size[1]];
+                    let target = self.hit_test(pos);
+                    self.focus_at(target);
+                    if let Some(target) = target {
+                        self.touches.push((touch, target));
+                    }
+                    target
+                }
+                _ => self.touches.iter().find(|&&(t, _)| t == touch).map(|&(_, id)| id),
+            };
+            match args.touch {
+                Touch::End | Touch::Cancel => self.touches.retain(|&(t, _)| t != touch),
+                _ => {}
+            }
+            if let Some(target) = target {
+                self.route(target, e, &mut f);
+            }
+            return;
+        }
+
+        if let Ok(Some(args)) = e.try_pen_args() {
+            if let Some(target) = self.hit_test(args.position()) {
+                self.route(target, e, &mut f);
+            }
+            return;
+        }
+
+        let press = e.try_press_button_args().unwrap_or(None).map(|args| args.button);
+        let release = e.try_release_button_args().unwrap_or(None).map(|args| args.button);
+        let button = press.or(release);
+        let mouse = match button {
+            Some(Button::Mouse(_)) => true,
+            _ => e.try_mouse_cursor_full_args().unwrap_or(None).is_some() ||
+                 e.try_mouse_scroll_full_args().unwrap_or(None).is_some(),
+        };
+        if mouse {
+            let target = match self.capture {
+                Some(id) => Some(id),
+                None => {
+                    let target = self.hit_test(self.cursor);
+                    if let Some(Button::Mouse(_)) = press {
+                        self.focus_at(target);
+                        self.capture = target;
+                    }
+                    target
+                }
+            };
+            if self.capture.is_some() {
+                if let Some(Button::Mouse(button)) = press {
+                    if !self.captured_buttons.contains(&button) {
+                        self.captured_buttons.push(button);
+                    }
+                }
+            }
+            if let Some(Button::Mouse(button)) = release {
+                self.captured_buttons.retain(|&b| b != button);
+                if self.captured_buttons.is_empty() { self.capture = None; }
+            }
+            if let Some(target) = target {
+                self.route(target, e, &mut f);
+            }
+            return;
+        }
+
+        let keyboard = match button {
+            Some(Button::Keyboard(_)) => true,
+            _ => e.try_text_args().unwrap_or(None).is_some() ||
+                 e.try_composition_args().unwrap_or(None).is_some(),
+        };
+        if !keyboard { return; }
+        let handled = match self.focus {
+            Some(focus) => self.route(focus, e, &mut f),
+            None => false,
+        };
+        if !handled {
+            if let Some(Button::Keyboard(Key::Tab)) = press {
+                if self.modifiers.contains(SHIFT) {
+                    self.focus_prev();
+                } else {
+                    self.focus_next();
+                }
+            }
+        }
+    }
+
+    // Focuses the nearest focusable node at or above the target.
+    fn focus_at(&mut self, target: Option<NodeId>) {
+        self.focus = target.and_then(|id| {
+            self.path(id).into_iter().rev().find(|&x| self.node(x).focusable)
+        });
+    }
+
+    // Calls closure for the nodes on the route to the target.
+    // Returns true if the event was handled.
+    fn route<E, F>(&self, target: NodeId, e: &E, f: &mut F) -> bool
+        where F: FnMut(NodeId, DispatchPhase, &E) -> bool
+    {
+        let path = self.path(target);
+        let ancestors = &path[..path.len() - 1];
+        for &id in ancestors {
+            if f(id, DispatchPhase::Capture, e) { return true; }
+        }
+        if f(target, DispatchPhase::Target, e) { return true; }
+        for &id in ancestors.iter().rev() {
+            if f(id, DispatchPhase::Bubble, e) { return true; }
+        }
+        false
+    }
+
+    // Returns the nodes from the root to the node.
+    fn path(&self, id: NodeId) -> Vec<NodeId> {
+        let mut path = vec![id];
+        let mut parent = self.node(id).parent;
+        while let Some(id) = parent {
+            path.push(id);
+            parent = self.node(id).parent;
+        }
+        path.reverse();
+        path
+    }
+
+    // Returns the focusable nodes in depth-first order.
+    fn focus_order(&self) -> Vec<NodeId> {
+        let mut order = vec![];
+        let mut stack: Vec<NodeId> = self.roots.iter().rev().map(|&id| id).collect();
+        while let Some(id) = stack.pop() {
+            let node = self.node(id);
+            if node.focusable { order.push(id); }
+            stack.extend(node.children.iter().rev().map(|&id| id));
+        }
+        order
+    }
+
+    fn get(&self, id: NodeId) -> Option<&Node> {
+        match self.nodes.get(id.0) {
+            Some(slot) if slot.generation == id.1 => slot.node.as_ref(),
+            _ => None,
+        }
+    }
+
+    fn get_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+        match self.nodes.get_mut(id.0) {
+            Some(slot) if slot.generation == id.1 => slot.node.as_mut(),
+            _ => None,
+        }
+    }
+
+    // Gets a node that is known to be in the dispatcher.
+    fn node(&self, id: NodeId) -> &Node {
+        self.get(id).expect("Unknown node")
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> &mut Node {
+        self.get_mut(id).expect("Unknown node")
+    }
+}
+
+impl Default for Dispatcher {
+    fn default() -> Dispatcher { Dispatcher::new() }
+}
+
+fn inside(rect: [f64; 4], pos: [f64; 2]) -> bool {
+    pos[0] >= rect[0] && pos[0] < rect[0] + rect[2] &&
+    pos[1] >= rect[1] && pos[1] < rect[1] + rect[3]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dispatch_pointer() {
+        use { Button, Event, Input, Motion, MouseButton };
+
+        let mut dispatcher = Dispatcher::new();
+        let root = dispatcher.add(None, [0.0, 0.0, 100.0, 100.0]);
+        let panel = dispatcher.add(Some(root), [10.0, 10.0, 50.0, 50.0]);
+        let button = dispatcher.add(Some(panel), [20.0, 20.0, 10.0, 10.0]);
+        let overlay = dispatcher.add(Some(panel), [25.0, 25.0, 10.0, 10.0]);
+        dispatcher.set_focusable(panel, true);
+        assert_eq!(dispatcher.hit_test([22.0, 22.0]), Some(button));
+        assert_eq!(dispatcher.hit_test([27.0, 27.0]), Some(overlay));
+        assert_eq!(dispatcher.hit_test([70.0, 70.0]), Some(root));
+        assert_eq!(dispatcher.hit_test([200.0, 0.0]), None);
+
+        let mut route = vec![];
+        let cursor: Event = Event::Input(Input::Move(Motion::MouseCursor((22.0, 22.0).into())));
+        dispatcher.event(&cursor, |id, phase, _| { route.push((id, phase)); false });
+        assert_eq!(route, vec![
+            (root, DispatchPhase::Capture),
+            (panel, DispatchPhase::Capture),
+            (button, DispatchPhase::Target),
+            (panel, DispatchPhase::Bubble),
+            (root, DispatchPhase::Bubble),
+        ]);
+
+        // Handling the event stops the routing.
+        route.clear();
+        let press: Event = Event::Input(Input::Press(Button::Mouse(MouseButton::Left).into()));
+        dispatcher.event(&press, |id, phase, _| { route.push((id, phase)); id == panel });
+        assert_eq!(route, vec![
+            (root, DispatchPhase::Capture),
+            (panel, DispatchPhase::Capture),
+        ]);
+        assert_eq!(dispatcher.focus(), Some(panel));
+
+        dispatcher.remove(panel);
+        assert!(!dispatcher.has_node(button));
+        assert_eq!(dispatcher.focus(), None);
+        assert_eq!(dispatcher.hit_test([22.0, 22.0]), Some(root));
+    }
+
+    #[test]
+    fn test_dispatch_focus() {
+        use { Button, Event, Input, Key };
+
+        let mut dispatcher = Dispatcher::new();
+        let form = dispatcher.add(None, [0.0, 0.0, 100.0, 100.0]);
+        let a = dispatcher.add(Some(form), [0.0, 0.0, 100.0, 10.0]);
+        let b = dispatcher.add(Some(form), [0.0, 10.0, 100.0, 10.0]);
+        let c = dispatcher.add(None, [0.0, 0.0, 10.0, 10.0]);
+        for &id in &[a, b, c] { dispatcher.set_focusable(id, true); }
+
+        let press = |key| -> Event { Event::Input(Input::Press(Button::Keyboard(key).into())) };
+        let release = |key| -> Event { Event::Input(Input::Release(Button::Keyboard(key).into())) };
+        let mut targets = vec![];
+        let tab = |dispatcher: &mut Dispatcher| {
+            dispatcher.event(&press(Key::Tab), |_, _, _| false);
+            dispatcher.focus()
+        };
+        assert_eq!(tab(&mut dispatcher), Some(a));
+        assert_eq!(tab(&mut dispatcher), Some(b));
+        assert_eq!(tab(&mut dispatcher), Some(c));
+        assert_eq!(tab(&mut dispatcher), Some(a));
+        dispatcher.event(&press(Key::LShift), |_, _, _| false);
+        assert_eq!(tab(&mut dispatcher), Some(c));
+        dispatcher.event(&release(Key::LShift), |_, _, _| false);
+
+        // A handled tab does not move focus.
+        dispatcher.event(&press(Key::Tab), |id, phase, _| {
+            targets.push(id);
+            phase == DispatchPhase::Target
+        });
+        assert_eq!(targets, vec![c]);
+        assert_eq!(dispatcher.focus(), Some(c));
+
+        targets.clear();
+        let text: Event = Event::Input(Input::Text("x".into()));
+        dispatcher.set_focus(Some(b));
+        dispatcher.event(&text, |id, _, _| { targets.push(id); false });
+        assert_eq!(targets, vec![form, b, form]);
+    }
+
+    #[test]
+    fn test_dispatch_capture() {
+        use { Button, Event, Input, Motion, MouseButton, Touch, TouchArgs };
+
+        let mut dispatcher = Dispatcher::new();
+        let root = dispatcher.add(None, [0.0, 0.0, 100.0, 100.0]);
+        let slider = dispatcher.add(Some(root), [0.0, 0.0, 10.0, 10.0]);
+        let mut targets = vec![];
+        {
+            let mut e = |e: Event| dispatcher.event(&e, |id, phase, _| {
+                if phase == DispatchPhase::Target { targets.push(id); }
+                false
+            });
+            let cursor = |x: f64| -> Event {
+                Event::Input(Input::Move(Motion::MouseCursor((x, 5.0).into())))
+            };
+            let left = Button::Mouse(MouseButton::Left);
+            let right = Button::Mouse(MouseButton::Right);
+            e(cursor(5.0));
+            e(Event::Input(Input::Press(left.into())));
+            e(cursor(50.0));
+            e(Event::Input(Input::Release(left.into())));
+            e(cursor(60.0));
+
+            // The pointer is captured until all buttons are released.
+            e(cursor(5.0));
+            e(Event::Input(Input::Press(left.into())));
+            e(Event::Input(Input::Press(right.into())));
+            e(Event::Input(Input::Release(left.into())));
+            e(cursor(50.0));
+            e(Event::Input(Input::Release(right.into())));
+            e(cursor(60.0));
+
+            // A touch goes to the node where it started.
+            let touch = |x: f64, touch| -> Event {
+                Event::Input(Input::Move(Motion::Touch(
+                    TouchArgs::new(0, 1, [x, 0.05], 1.0, touch))))
+            };
+            e(Event::Input(Input::Resize(100, 100)));
+            e(touch(0.05, Touch::Start));
+            e(touch(0.5, Touch::Move));
+            e(touch(0.5, Touch::End));
+            e(touch(0.5, Touch::Move));
+        }
+        assert_eq!(targets, vec![
+            slider, slider, slider, slider, root,
+            slider, slider, slider, slider, slider, slider, root,
+            slider, slider, slider,
+        ]);
+    }
+
+    #[test]
+    fn test_dispatch_stale_node() {
+        let mut dispatcher = Dispatcher::new();
+        let root = dispatcher.add(None, [0.0, 0.0, 100.0, 100.0]);
+        let child = dispatcher.add(Some(root), [0.0, 0.0, 10.0, 10.0]);
+        dispatcher.set_focusable(child, true);
+        assert!(!dispatcher.set_focus(Some(root)));
+        assert!(dispatcher.set_focus(Some(child)));
+        assert!(dispatcher.remove(child));
+        assert!(!dispatcher.remove(child));
+        assert_eq!(dispatcher.rect(child), None);
+        assert!(!dispatcher.set_rect(child, [0.0; 4]));
+        assert!(!dispatcher.set_focusable(child, true));
+        assert!(!dispatcher.set_focus(Some(child)));
+        assert_eq!(dispatcher.rect(root), Some([0.0, 0.0, 100.0, 100.0]));
+
+        // The storage of removed nodes is reused, but not their ids.
+        let other = dispatcher.add(Some(root), [0.0, 0.0, 20.0, 20.0]);
+        assert!(other != child);
+        assert_eq!(dispatcher.nodes.len(), 2);
+        assert!(!dispatcher.has_node(child));
+        assert!(!dispatcher.set_rect(child, [0.0; 4]));
+        assert_eq!(dispatcher.rect(other), Some([0.0, 0.0, 20.0, 20.0]));
+    }
+}
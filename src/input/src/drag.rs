@@ -0,0 +1,195 @@
+//! Detects dragging with mouse buttons.
+//!
+//! Positions are in window coordinates.
+
+use std::default::Default;
+
+use { Button, GenericEvent, MouseButton };
+
+/// A change in dragging with a mouse button.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Drag {
+    /// The cursor moved further than the threshold while the button was held.
+    Start {
+        /// The mouse button.
+        button: MouseButton,
+        /// The cursor position when the button was pressed.
+        pos: [f64; 2],
+    },
+    /// The cursor moved while dragging.
+    Move {
+        /// The mouse button.
+        button: MouseButton,
+        /// The cursor position.
+        pos: [f64; 2],
+        /// The motion since the previous move.
+        delta: [f64; 2],
+        /// The motion since the button was pressed.
+        offset: [f64; 2],
+    },
+    /// The button was released.
+    End {
+        /// The mouse button.
+        button: MouseButton,
+        /// The cursor position.
+        pos: [f64; 2],
+        /// The motion since the button was pressed.
+        offset: [f64; 2],
+    },
+    /// The drag was cancelled because the window lost focus.
+    Cancel {
+        /// The mouse button.
+        button: MouseButton,
+    },
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Held {
+    button: MouseButton,
+    start: [f64; 2],
+    last: [f64; 2],
+    dragging: bool,
+}
+
+/// Turns mouse button presses, cursor motion and releases into drags.
+///
+/// Each mouse button is tracked separately.
+#[derive(Clone, Debug)]
+pub struct DragTracker {
+    /// How far the cursor must move after pressing a button
+    /// before dragging starts.
+    pub threshold: f64,
+    cursor: [f64; 2],
+    held: Vec<Held>,
+}
+
+impl DragTracker {
+    /// Creates a new drag tracker.
+    pub fn new(threshold: f64) -> DragTracker {
+        DragTracker {
+            threshold: threshold,
+            cursor: [0.0; 2],
+            held: vec![],
+        }
+    }
+
+    /// Change drag state depending on event.
+    ///
+    /// Calls closure for every change in dragging.
+    /// When the window loses focus, drags in progress are cancelled.
+    /// Events with arguments of an unexpected type are ignored.
+    pub fn event<E, F>(&mut self, e: &E, mut f: F)
+        where E: GenericEvent, F: FnMut(Drag)
+    {
+        use { FocusEvent, MouseCursorEvent, PressEvent, ReleaseEvent };
+
+        if let Ok(Some(args)) = e.try_mouse_cursor_full_args() {
+            let pos = [args.x, args.y];
+            self.cursor = pos;
+            for h in &mut self.held {
+                if !h.dragging {
+                    let (dx, dy) = (pos[0] - h.start[0], pos[1] - h.start[1]);
+                    if (dx * dx + dy * dy).sqrt() <= self.threshold { continue; }
+                    h.dragging = true;
+                    f(Drag::Start { button: h.button, pos: h.start });
+                }
+                f(Drag::Move {
+                    button: h.button,
+                    pos: pos,
+                    delta: [pos[0] - h.last[0], pos[1] - h.last[1]],
+                    offset: [pos[0] - h.start[0], pos[1] - h.start[1]],
+                });
+                h.last = pos;
+            }
+        }
+        let press = e.try_press_button_args().unwrap_or(None).map(|args| args.button);
+        if let Some(Button::Mouse(button)) = press {
+            if self.held.iter().all(|h| h.button != button) {
+                self.held.push(Held {
+                    button: button,
+                    start: self.cursor,
+                    last: self.cursor,
+                    dragging: false,
+                });
+            }
+        }
+        let release = e.try_release_button_args().unwrap_or(None).map(|args| args.button);
+        if let Some(Button::Mouse(button)) = release {
+            if let Some(i) = self.held.iter().position(|h| h.button == button) {
+                let h = self.held.remove(i);
+                if h.dragging {
+                    let pos = self.cursor;
+                    f(Drag::End {
+                        button: button,
+                        pos: pos,
+                        offset: [pos[0] - h.start[0], pos[1] - h.start[1]],
+                    });
+                }
+            }
+        }
+        if let Ok(Some(false)) = e.try_focus_args() {
+            for h in self.held.drain(..) {
+                if h.dragging {
+                    f(Drag::Cancel { button: h.button });
+                }
+            }
+        }
+    }
+
+    /// Returns true if dragging with the mouse button.
+    pub fn is_dragging(&self, button: MouseButton) -> bool {
+        self.held.iter().any(|h| h.button == button && h.dragging)
+    }
+}
+
+impl Default for DragTracker {
+    fn default() -> DragTracker { DragTracker::new(4.0) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drag() {
+        use { Button, Event, Input, Motion, MouseButton };
+
+        let left = Button::Mouse(MouseButton::Left);
+        let cursor = |x: f64| -> Event {
+            Event::Input(Input::Move(Motion::MouseCursor((x, 0.0).into())))
+        };
+        let mut tracker = DragTracker::new(4.0);
+        let mut drags = vec![];
+        tracker.event(&Event::Input(Input::Press(left.into())), |d| drags.push(d));
+        tracker.event(&cursor(3.0), |d| drags.push(d));
+        assert!(drags.is_empty());
+        tracker.event(&cursor(5.0), |d| drags.push(d));
+        tracker.event(&cursor(7.0), |d| drags.push(d));
+        tracker.event(&Event::Input(Input::Release(left.into())), |d| drags.push(d));
+        assert_eq!(drags, vec![
+            Drag::Start { button: MouseButton::Left, pos: [0.0, 0.0] },
+            Drag::Move { button: MouseButton::Left, pos: [5.0, 0.0],
+                         delta: [5.0, 0.0], offset: [5.0, 0.0] },
+            Drag::Move { button: MouseButton::Left, pos: [7.0, 0.0],
+                         delta: [2.0, 0.0], offset: [7.0, 0.0] },
+            Drag::End { button: MouseButton::Left, pos: [7.0, 0.0], offset: [7.0, 0.0] },
+        ]);
+    }
+
+    #[test]
+    fn test_drag_focus() {
+        use { Button, Event, Input, Motion, MouseButton };
+
+        let right = Button::Mouse(MouseButton::Right);
+        let mut tracker = DragTracker::default();
+        let mut drags = vec![];
+        tracker.event(&Event::Input(Input::Press(right.into())), |d| drags.push(d));
+        tracker.event(&Event::Input(Input::Move(Motion::MouseCursor((0.0, 10.0).into()))),
+                      |d| drags.push(d));
+        assert!(tracker.is_dragging(MouseButton::Right));
+        drags.clear();
+        tracker.event(&Event::Input(Input::Focus(false)), |d| drags.push(d));
+        assert_eq!(drags, vec![Drag::Cancel { button: MouseButton::Right }]);
+        assert!(!tracker.is_dragging(MouseButton::Right));
+    }
+}
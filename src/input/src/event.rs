@@ -1,4 +1,6 @@
 use {
+    CustomArgs,
+    EventId,
     IdleArgs,
     Input,
     RenderArgs,
@@ -19,6 +21,27 @@ pub enum Event<I = Input> {
     Idle(IdleArgs),
     /// Input event.
     Input(I),
+    /// Custom event defined by a library, see [`CustomEvent`](./trait.CustomEvent.html).
+    Custom(EventId, CustomArgs),
+}
+
+impl<I> Event<I> {
+    /// Maps the input event to another type.
+    ///
+    /// Returns `None` if the closure drops the input event.
+    /// Other events are kept.
+    pub fn filter_map_input<J, F>(self, f: F) -> Option<Event<J>>
+        where F: FnOnce(I) -> Option<J>
+    {
+        match self {
+            Event::Render(args) => Some(Event::Render(args)),
+            Event::AfterRender(args) => Some(Event::AfterRender(args)),
+            Event::Update(args) => Some(Event::Update(args)),
+            Event::Idle(args) => Some(Event::Idle(args)),
+            Event::Input(input) => f(input).map(|input| Event::Input(input)),
+            Event::Custom(event_id, args) => Some(Event::Custom(event_id, args)),
+        }
+    }
 }
 
 impl<I> From<RenderArgs> for Event<I> {
@@ -0,0 +1,98 @@
+//! Dragging files onto a window.
+
+use std::any::Any;
+use std::path::PathBuf;
+
+use { ArgsError, GenericEvent, FILE_DRAG };
+use generic_event::try_with_args;
+
+/// A change while dragging files onto a window.
+#[derive(Clone, RustcDecodable, RustcEncodable, PartialEq, Debug)]
+pub enum FileDrag {
+    /// Files are dragged over the window.
+    Hover {
+        /// The paths of the files.
+        paths: Vec<PathBuf>,
+        /// The cursor position in window coordinates.
+        pos: [f64; 2],
+    },
+    /// Files were dropped onto the window.
+    Drop {
+        /// The paths of the files.
+        paths: Vec<PathBuf>,
+        /// The cursor position in window coordinates.
+        pos: [f64; 2],
+    },
+    /// The files were dragged out of the window, or the drag was cancelled.
+    Cancel,
+}
+
+/// Dragging files onto a window.
+pub trait FileDragEvent: Sized {
+    /// Creates a file drag event.
+    fn from_file_drag(args: &FileDrag, old_event: &Self) -> Option<Self>;
+    /// Calls closure if this is a file drag event.
+    fn file_drag<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(&FileDrag) -> U;
+    /// Returns file drag arguments.
+    fn file_drag_args(&self) -> Option<FileDrag> {
+        self.file_drag(|args| args.clone())
+    }
+    /// Returns file drag arguments, or an error if they have an unexpected type.
+    fn try_file_drag_args(&self) -> Result<Option<FileDrag>, ArgsError>;
+}
+
+impl<T: GenericEvent> FileDragEvent for T {
+    fn from_file_drag(args: &FileDrag, old_event: &Self) -> Option<Self> {
+        GenericEvent::from_args(FILE_DRAG, args as &Any, old_event)
+    }
+
+    fn file_drag<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(&FileDrag) -> U
+    {
+        try_with_args(self, FILE_DRAG, "FileDrag", f)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_file_drag_args(&self) -> Result<Option<FileDrag>, ArgsError> {
+        try_with_args(self, FILE_DRAG, "FileDrag", |args: &FileDrag| args.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_file_drag() {
+        use std::path::PathBuf;
+        use super::super::Input;
+
+        let e = Input::FileDrag(FileDrag::Cancel);
+        let args = FileDrag::Drop {
+            paths: vec![PathBuf::from("assets/level.json")],
+            pos: [10.0, 20.0],
+        };
+        let x: Option<Input> = FileDragEvent::from_file_drag(&args, &e);
+        let y: Option<Input> = x.clone().unwrap().file_drag(|args|
+            FileDragEvent::from_file_drag(args, x.as_ref().unwrap())).unwrap();
+        assert_eq!(x, y);
+    }
+
+    #[test]
+    fn test_event_file_drag() {
+        use std::path::PathBuf;
+        use Event;
+        use super::super::Input;
+
+        let e = Event::Input(Input::FileDrag(FileDrag::Cancel));
+        let args = FileDrag::Hover {
+            paths: vec![PathBuf::from("a.png"), PathBuf::from("b.png")],
+            pos: [0.0, 0.0],
+        };
+        let x: Option<Event> = FileDragEvent::from_file_drag(&args, &e);
+        let y: Option<Event> = x.clone().unwrap().file_drag(|args|
+            FileDragEvent::from_file_drag(args, x.as_ref().unwrap())).unwrap();
+        assert_eq!(x, y);
+    }
+}
@@ -1,6 +1,7 @@
 use std::any::Any;
 
-use { GenericEvent, FOCUS };
+use { ArgsError, GenericEvent, FOCUS };
+use generic_event::try_with_args;
 
 /// When window gets or loses focus
 pub trait FocusEvent: Sized {
@@ -13,6 +14,8 @@ pub trait FocusEvent: Sized {
     fn focus_args(&self) -> Option<bool> {
         self.focus(|val| val)
     }
+    /// Returns focus arguments, or an error if they have an unexpected type.
+    fn try_focus_args(&self) -> Result<Option<bool>, ArgsError>;
 }
 
 impl<T: GenericEvent> FocusEvent for T {
@@ -23,16 +26,12 @@ impl<T: GenericEvent> FocusEvent for T {
     fn focus<U, F>(&self, mut f: F) -> Option<U>
         where F: FnMut(bool) -> U
     {
-        if self.event_id() != FOCUS {
-            return None;
-        }
-        self.with_args(|any| {
-            if let Some(&focused) = any.downcast_ref::<bool>() {
-                Some(f(focused))
-            } else {
-                panic!("Expected bool")
-            }
-        })
+        try_with_args(self, FOCUS, "bool", |&focused: &bool| f(focused))
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_focus_args(&self) -> Result<Option<bool>, ArgsError> {
+        try_with_args(self, FOCUS, "bool", |&focused: &bool| focused)
     }
 }
 
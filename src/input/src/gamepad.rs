@@ -0,0 +1,520 @@
+//! Standard gamepad layout.
+//!
+//! Controllers report buttons and axes by index,
+//! and the same index means different things on different devices.
+//! A `GamepadMapping` translates the indices of one device
+//! to a standard layout, named after the position of buttons
+//! instead of their labels.
+//!
+//! Mappings can be loaded from the SDL GameControllerDB text format,
+//! see https://github.com/gabomdq/SDL_GameControllerDB.
+//! They are looked up by the GUID in `ControllerConnection::Connected`,
+//! see `GamepadDb::find`.
+//! Half axes, written with `+` or `-`, are supported.
+//! Hats are not reported by backends, so hat mappings are ignored.
+
+use std::collections::HashMap;
+
+use { ControllerAxisArgs, ControllerButton, ControllerConnection };
+
+/// A button in the standard gamepad layout.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq, Eq, Hash, Debug)]
+pub enum GamepadButton {
+    /// The bottom face button, for example A on Xbox controllers.
+    South,
+    /// The right face button, for example B on Xbox controllers.
+    East,
+    /// The left face button, for example X on Xbox controllers.
+    West,
+    /// The top face button, for example Y on Xbox controllers.
+    North,
+    /// The back or select button.
+    Back,
+    /// The guide or home button.
+    Guide,
+    /// The start button.
+    Start,
+    /// Pressing the left stick.
+    LeftStick,
+    /// Pressing the right stick.
+    RightStick,
+    /// The left shoulder button.
+    LeftShoulder,
+    /// The right shoulder button.
+    RightShoulder,
+    /// The left trigger, on devices that report it as a button.
+    LeftTrigger,
+    /// The right trigger, on devices that report it as a button.
+    RightTrigger,
+    /// Up on the directional pad.
+    DPadUp,
+    /// Down on the directional pad.
+    DPadDown,
+    /// Left on the directional pad.
+    DPadLeft,
+    /// Right on the directional pad.
+    DPadRight,
+}
+
+impl GamepadButton {
+    /// Gets button from its name in the SDL GameControllerDB format.
+    pub fn from_sdl_name(name: &str) -> Option<GamepadButton> {
+        use self::GamepadButton::*;
+
+        Some(match name {
+            "a" => South,
+            "b" => East,
+            "x" => West,
+            "y" => North,
+            "back" => Back,
+            "guide" => Guide,
+            "start" => Start,
+            "leftstick" => LeftStick,
+            "rightstick" => RightStick,
+            "leftshoulder" => LeftShoulder,
+            "rightshoulder" => RightShoulder,
+            "lefttrigger" => LeftTrigger,
+            "righttrigger" => RightTrigger,
+            "dpup" => DPadUp,
+            "dpdown" => DPadDown,
+            "dpleft" => DPadLeft,
+            "dpright" => DPadRight,
+            _ => { return None; }
+        })
+    }
+}
+
+/// An axis in the standard gamepad layout.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq, Eq, Hash, Debug)]
+pub enum GamepadAxis {
+    /// The horizontal position of the left stick.
+    LeftX,
+    /// The vertical position of the left stick.
+    LeftY,
+    /// The horizontal position of the right stick.
+    RightX,
+    /// The vertical position of the right stick.
+    RightY,
+    /// The left trigger.
+    LeftTrigger,
+    /// The right trigger.
+    RightTrigger,
+}
+
+impl GamepadAxis {
+    /// Gets axis from its name in the SDL GameControllerDB format.
+    pub fn from_sdl_name(name: &str) -> Option<GamepadAxis> {
+        use self::GamepadAxis::*;
+
+        Some(match name {
+            "leftx" => LeftX,
+            "lefty" => LeftY,
+            "rightx" => RightX,
+            "righty" => RightY,
+            "lefttrigger" => LeftTrigger,
+            "righttrigger" => RightTrigger,
+            _ => { return None; }
+        })
+    }
+}
+
+// A part of an axis.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum AxisRange {
+    Full,
+    Positive,
+    Negative,
+}
+
+impl AxisRange {
+    // Splits off a `+` or `-` prefix for half of an axis.
+    fn parse_prefix(text: &str) -> (AxisRange, &str) {
+        if text.starts_with('+') {
+            (AxisRange::Positive, &text[1..])
+        } else if text.starts_with('-') {
+            (AxisRange::Negative, &text[1..])
+        } else {
+            (AxisRange::Full, text)
+        }
+    }
+}
+
+// What a device axis is mapped to.
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum AxisTarget {
+    Axis(GamepadAxis, AxisRange),
+    Button(GamepadButton),
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct AxisMapping {
+    range: AxisRange,
+    invert: bool,
+    target: AxisTarget,
+}
+
+impl AxisMapping {
+    // Returns the position within the range of the device axis, 0..1 for half axes.
+    fn source_position(&self, position: f64) -> f64 {
+        let position = if self.invert { -position } else { position };
+        match self.range {
+            AxisRange::Full => position,
+            AxisRange::Positive => position.max(0.0),
+            AxisRange::Negative => (-position).max(0.0),
+        }
+    }
+}
+
+/// Translates the button and axis indices of a device to the standard layout.
+///
+/// Device axes can be split in halves, for example when both triggers share one axis,
+/// or when the directional pad is reported as axes.
+/// Buttons can be mapped to half of a standard axis.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GamepadMapping {
+    /// The device GUID, as a hexadecimal string.
+    pub guid: String,
+    /// The device name.
+    pub name: String,
+    /// The platform the mapping is for, if any.
+    pub platform: Option<String>,
+    buttons: HashMap<u8, GamepadButton>,
+    // Buttons mapped to a standard axis, with the position when pressed.
+    button_axes: HashMap<u8, (GamepadAxis, f64)>,
+    axes: HashMap<u8, Vec<AxisMapping>>,
+}
+
+impl GamepadMapping {
+    /// Creates a mapping without buttons or axes.
+    pub fn new<G, N>(guid: G, name: N) -> GamepadMapping
+        where G: Into<String>, N: Into<String>
+    {
+        GamepadMapping {
+            guid: guid.into(),
+            name: name.into(),
+            platform: None,
+            buttons: HashMap::new(),
+            button_axes: HashMap::new(),
+            axes: HashMap::new(),
+        }
+    }
+
+    /// Parses a line in the SDL GameControllerDB format.
+    ///
+    /// For example: `<guid>,<name>,a:b0,b:b1,leftx:a0,dpup:-a7,platform:Linux,`
+    pub fn parse(line: &str) -> Result<GamepadMapping, String> {
+        let mut parts = line.trim().split(',');
+        let guid = match parts.next() {
+            Some(guid) if guid.len() > 0 => guid,
+            _ => { return Err(format!("Expected GUID in mapping `{}`", line)); }
+        };
+        let name = match parts.next() {
+            Some(name) => name,
+            None => { return Err(format!("Expected name in mapping `{}`", line)); }
+        };
+        let mut mapping = GamepadMapping::new(guid, name);
+        for part in parts {
+            if part.len() == 0 { continue; }
+            let mut kv = part.splitn(2, ':');
+            let (key, value) = match (kv.next(), kv.next()) {
+                (Some(key), Some(value)) => (key, value),
+                _ => { return Err(format!("Expected `key:value`, found `{}`", part)); }
+            };
+            if key == "platform" {
+                mapping.platform = Some(value.to_owned());
+                continue;
+            }
+            // A `+` or `-` before the key means half of a standard axis.
+            let (output_range, key) = AxisRange::parse_prefix(key);
+            let button = if output_range == AxisRange::Full {
+                GamepadButton::from_sdl_name(key)
+            } else {
+                None
+            };
+            let axis = GamepadAxis::from_sdl_name(key);
+            // Unknown keys from newer versions of the format are ignored,
+            // before parsing the value, since it might have another syntax.
+            if button.is_none() && axis.is_none() { continue; }
+            // Hats are not reported by backends.
+            if value.starts_with('h') { continue; }
+            if value.starts_with('b') {
+                let index = try!(parse_index(&value[1..], part));
+                match (button, axis) {
+                    (Some(button), _) => {
+                        mapping.buttons.insert(index, button);
+                    }
+                    (None, Some(axis)) => {
+                        let pressed = if output_range == AxisRange::Negative { -1.0 } else { 1.0 };
+                        mapping.button_axes.insert(index, (axis, pressed));
+                    }
+                    (None, None) => {}
+                }
+                continue;
+            }
+            let (range, value) = AxisRange::parse_prefix(value);
+            let invert = value.ends_with('~');
+            let value = value.trim_right_matches('~');
+            if !value.starts_with('a') {
+                return Err(format!("Expected button, axis or hat in `{}`", part));
+            }
+            let index = try!(parse_index(&value[1..], part));
+            // Triggers are named like buttons, but are mapped as axes when possible.
+            let target = match (axis, button) {
+                (Some(axis), _) => AxisTarget::Axis(axis, output_range),
+                (None, Some(button)) => AxisTarget::Button(button),
+                (None, None) => continue,
+            };
+            mapping.axes.entry(index).or_insert(vec![]).push(AxisMapping {
+                range: range,
+                invert: invert,
+                target: target,
+            });
+        }
+        Ok(mapping)
+    }
+
+    /// Maps a button index to a standard button.
+    pub fn set_button(&mut self, index: u8, button: GamepadButton) {
+        self.buttons.insert(index, button);
+    }
+
+    /// Maps an axis index to a standard axis.
+    ///
+    /// This replaces other mappings of the axis index.
+    pub fn set_axis(&mut self, index: u8, axis: GamepadAxis, invert: bool) {
+        self.axes.insert(index, vec![AxisMapping {
+            range: AxisRange::Full,
+            invert: invert,
+            target: AxisTarget::Axis(axis, AxisRange::Full),
+        }]);
+    }
+
+    /// Translates a controller button.
+    ///
+    /// Returns `None` for buttons mapped to an axis, see `button_axis`.
+    pub fn button(&self, button: ControllerButton) -> Option<GamepadButton> {
+        self.buttons.get(&button.button).map(|&b| b)
+    }
+
+    /// Translates a controller button mapped to half of a standard axis.
+    ///
+    /// Returns the standard axis and its position, which is zero when released.
+    pub fn button_axis(&self, button: ControllerButton, pressed: bool)
+        -> Option<(GamepadAxis, f64)>
+    {
+        self.button_axes.get(&button.button).map(|&(axis, position)|
+            (axis, if pressed { position } else { 0.0 }))
+    }
+
+    /// Translates a controller axis and its position to standard axes.
+    ///
+    /// Half of a device axis maps to positions 0..1 of a standard axis,
+    /// and a device axis mapped to half of a standard axis is scaled to fit.
+    pub fn axes(&self, args: ControllerAxisArgs) -> Vec<(GamepadAxis, f64)> {
+        let mappings = match self.axes.get(&args.axis) {
+            Some(mappings) => mappings,
+            None => return vec![],
+        };
+        mappings.iter().filter_map(|m| {
+            let (axis, output_range) = match m.target {
+                AxisTarget::Axis(axis, output_range) => (axis, output_range),
+                AxisTarget::Button(_) => return None,
+            };
+            let position = m.source_position(args.position);
+            let position = match (m.range, output_range) {
+                (_, AxisRange::Full) => position,
+                (AxisRange::Full, AxisRange::Positive) => 0.5 * (position + 1.0),
+                (AxisRange::Full, AxisRange::Negative) => -0.5 * (position + 1.0),
+                (_, AxisRange::Positive) => position,
+                (_, AxisRange::Negative) => -position,
+            };
+            Some((axis, position))
+        }).collect()
+    }
+
+    /// Translates a controller axis mapped to standard buttons,
+    /// for example a directional pad reported as axes.
+    ///
+    /// Returns the buttons and whether they are pressed,
+    /// which is when the axis is past the middle of the mapped half.
+    pub fn axis_buttons(&self, args: ControllerAxisArgs) -> Vec<(GamepadButton, bool)> {
+        let mappings = match self.axes.get(&args.axis) {
+            Some(mappings) => mappings,
+            None => return vec![],
+        };
+        mappings.iter().filter_map(|m| match m.target {
+            AxisTarget::Button(button) =>
+                Some((button, m.source_position(args.position) > 0.5)),
+            AxisTarget::Axis(_, _) => None,
+        }).collect()
+    }
+}
+
+fn parse_index(text: &str, part: &str) -> Result<u8, String> {
+    text.parse().map_err(|_| format!("Expected index in `{}`", part))
+}
+
+/// Stores gamepad mappings by device GUID.
+#[derive(Clone, Debug)]
+pub struct GamepadDb {
+    mappings: HashMap<String, GamepadMapping>,
+}
+
+impl GamepadDb {
+    /// Creates an empty database.
+    pub fn new() -> GamepadDb {
+        GamepadDb { mappings: HashMap::new() }
+    }
+
+    /// Parses mappings in the SDL GameControllerDB format, one per line.
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn parse(text: &str) -> Result<GamepadDb, String> {
+        let mut db = GamepadDb::new();
+        try!(db.add_mappings(text, None));
+        Ok(db)
+    }
+
+    /// Adds mappings in the SDL GameControllerDB format, one per line.
+    ///
+    /// When a platform is given, mappings for other platforms are skipped.
+    /// A mapping replaces an earlier one with the same GUID.
+    pub fn add_mappings(&mut self, text: &str, platform: Option<&str>) -> Result<(), String> {
+        for line in text.lines() {
+            let line = line.trim();
+            if line.len() == 0 || line.starts_with('#') { continue; }
+            let mapping = try!(GamepadMapping::parse(line));
+            if let (Some(platform), Some(ref p)) = (platform, mapping.platform.as_ref()) {
+                if &p[..] != platform { continue; }
+            }
+            self.insert(mapping);
+        }
+        Ok(())
+    }
+
+    /// Adds a mapping, replacing any with the same GUID.
+    pub fn insert(&mut self, mapping: GamepadMapping) {
+        self.mappings.insert(mapping.guid.clone(), mapping);
+    }
+
+    /// Gets the mapping for a device GUID.
+    pub fn get(&self, guid: &str) -> Option<&GamepadMapping> {
+        self.mappings.get(guid)
+    }
+
+    /// Finds a mapping by device name.
+    ///
+    /// Useful when the backend does not report GUIDs.
+    pub fn find_by_name(&self, name: &str) -> Option<&GamepadMapping> {
+        self.mappings.values().find(|m| m.name == name)
+    }
+
+    /// Finds the mapping for a connected controller.
+    ///
+    /// Looks up the GUID, falling back to the name when the GUID is
+    /// not reported or not found.
+    /// Returns `None` for disconnections.
+    pub fn find(&self, connection: &ControllerConnection) -> Option<&GamepadMapping> {
+        match *connection {
+            ControllerConnection::Connected { ref name, ref guid, .. } => {
+                guid.as_ref().and_then(|guid| self.get(guid))
+                    .or_else(|| self.find_by_name(name))
+            }
+            ControllerConnection::Disconnected { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DB: &'static str = "
+# Comment
+030000005e0400008e02000014010000,Xbox 360 Controller,a:b0,b:b1,x:b2,y:b3,dpup:h0.1,leftx:a0,lefty:a1~,lefttrigger:a2,platform:Linux,
+030000005e0400008e02000014010000,Xbox 360 Controller,a:b1,platform:Windows,
+";
+
+    #[test]
+    fn test_gamepad_db() {
+        use { ControllerAxisArgs, ControllerButton };
+
+        let db = GamepadDb::parse(DB).unwrap();
+        let mapping = db.get("030000005e0400008e02000014010000").unwrap();
+        assert_eq!(mapping.platform, Some("Windows".to_owned()));
+
+        let mut db = GamepadDb::new();
+        db.add_mappings(DB, Some("Linux")).unwrap();
+        let mapping = db.find_by_name("Xbox 360 Controller").unwrap();
+        assert_eq!(mapping.button(ControllerButton::new(0, 0)), Some(GamepadButton::South));
+        assert_eq!(mapping.button(ControllerButton::new(0, 3)), Some(GamepadButton::North));
+        assert_eq!(mapping.button(ControllerButton::new(0, 4)), None);
+        assert_eq!(mapping.axes(ControllerAxisArgs::new(0, 1, 0.5)),
+                   vec![(GamepadAxis::LeftY, -0.5)]);
+        assert_eq!(mapping.axes(ControllerAxisArgs::new(0, 2, 0.5)),
+                   vec![(GamepadAxis::LeftTrigger, 0.5)]);
+    }
+
+    #[test]
+    fn test_gamepad_db_find() {
+        use { ControllerButton, ControllerConnection };
+
+        let mut db = GamepadDb::new();
+        db.add_mappings(DB, Some("Windows")).unwrap();
+        let connected = |name: &str, guid: Option<&str>| ControllerConnection::Connected {
+            id: 0,
+            name: name.to_owned(),
+            guid: guid.map(|guid| guid.to_owned()),
+        };
+        let mapping = db.find(&connected("Pad", Some("030000005e0400008e02000014010000")))
+            .unwrap();
+        assert_eq!(mapping.button(ControllerButton::new(0, 1)), Some(GamepadButton::South));
+        assert!(db.find(&connected("Xbox 360 Controller", None)).is_some());
+        assert!(db.find(&connected("Pad", Some("unknown"))).is_none());
+        assert!(db.find(&ControllerConnection::Disconnected { id: 0 }).is_none());
+    }
+
+    #[test]
+    fn test_gamepad_mapping_half_axes() {
+        use { ControllerAxisArgs, ControllerButton };
+
+        let mapping = GamepadMapping::parse("guid,name,\
+lefttrigger:+a2,righttrigger:-a2,dpup:-a7,dpdown:+a7,-leftx:b4,+leftx:b5,").unwrap();
+        assert_eq!(mapping.axes(ControllerAxisArgs::new(0, 2, -0.5)), vec![
+            (GamepadAxis::LeftTrigger, 0.0),
+            (GamepadAxis::RightTrigger, 0.5),
+        ]);
+        assert_eq!(mapping.axis_buttons(ControllerAxisArgs::new(0, 7, -1.0)), vec![
+            (GamepadButton::DPadUp, true),
+            (GamepadButton::DPadDown, false),
+        ]);
+        assert_eq!(mapping.button(ControllerButton::new(0, 4)), None);
+        assert_eq!(mapping.button_axis(ControllerButton::new(0, 4), true),
+                   Some((GamepadAxis::LeftX, -1.0)));
+        assert_eq!(mapping.button_axis(ControllerButton::new(0, 5), false),
+                   Some((GamepadAxis::LeftX, 0.0)));
+    }
+
+    #[test]
+    fn test_gamepad_mapping_unknown_keys() {
+        use ControllerButton;
+
+        let db = GamepadDb::parse("\
+03000000c82d00000161000000000000,8BitDo SN30 Pro,crc:b2f1,a:b1,b:b0,back:b10,\
+dpdown:h0.4,dpleft:h0.8,dpright:h0.2,dpup:h0.1,leftshoulder:b6,leftstick:b13,\
+lefttrigger:b8,leftx:a0,lefty:a1,rightshoulder:b7,rightstick:b14,righttrigger:b9,\
+rightx:a3,righty:a4,start:b11,x:b4,y:b3,hint:!SDL_GAMECONTROLLER_USE_BUTTON_LABELS:=1,\
+platform:Windows,").unwrap();
+        let mapping = db.get("03000000c82d00000161000000000000").unwrap();
+        assert_eq!(mapping.button(ControllerButton::new(0, 1)), Some(GamepadButton::South));
+        assert_eq!(mapping.platform, Some("Windows".to_owned()));
+    }
+
+    #[test]
+    fn test_gamepad_mapping_errors() {
+        assert!(GamepadMapping::parse("").is_err());
+        assert!(GamepadMapping::parse("guid,name,a:bx").is_err());
+        assert!(GamepadMapping::parse("guid,name,a").is_err());
+        assert!(GamepadMapping::parse("guid,name,leftx:+x0").is_err());
+    }
+}
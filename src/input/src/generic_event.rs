@@ -2,12 +2,96 @@
 
 use std::borrow::ToOwned;
 use std::any::Any;
+use std::error::Error;
+use std::fmt;
 
-use {AfterRenderArgs, ControllerAxisArgs, Button, Event, EventId, IdleArgs, Input,
-     Motion, RenderArgs, TouchArgs, UpdateArgs};
-use {AFTER_RENDER, CONTROLLER_AXIS, CURSOR, FOCUS, IDLE, MOUSE_CURSOR,
-     MOUSE_RELATIVE, MOUSE_SCROLL, PRESS, RENDER, RELEASE, RESIZE,
-     TEXT, TOUCH, UPDATE};
+use {AfterRenderArgs, Composition, ControllerAxisArgs, ControllerConnection, CustomArgs,
+     Button, ButtonArgs, Event, EventId, FileDrag, IdleArgs, Input, Motion, MouseCursorArgs,
+     MouseRelativeArgs, PenArgs, RenderArgs, ScrollArgs, TouchArgs, UpdateArgs};
+use {AFTER_RENDER, COMPOSITION, CONTROLLER_AXIS, CONTROLLER_CONNECTION, CURSOR, FILE_DRAG,
+     FOCUS, IDLE, MOUSE_CURSOR, MOUSE_RELATIVE, MOUSE_SCROLL, PEN, PRESS, RENDER, RELEASE,
+     RESIZE, TEXT, TOUCH, UPDATE};
+
+/// The error returned when event arguments have an unexpected type.
+///
+/// This happens when a `GenericEvent` implementation passes arguments
+/// of another type than the event id requires.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ArgsError {
+    /// The id of the event.
+    pub event_id: EventId,
+    /// The name of the expected argument type.
+    pub expected: &'static str,
+}
+
+impl ArgsError {
+    /// Creates a new arguments error.
+    pub fn new(event_id: EventId, expected: &'static str) -> ArgsError {
+        ArgsError {
+            event_id: event_id,
+            expected: expected,
+        }
+    }
+}
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Expected {} for event `{}`", self.expected, self.event_id.0)
+    }
+}
+
+impl Error for ArgsError {
+    fn description(&self) -> &str {
+        "Event arguments have an unexpected type"
+    }
+}
+
+/// Calls closure with the arguments of an event, if it has the id.
+///
+/// Returns an error instead of panicking when the arguments do not have type `T`.
+/// The name of `T` is used in the error.
+pub fn try_with_args<E, T, U, F>(e: &E, event_id: EventId, expected: &'static str, mut f: F)
+    -> Result<Option<U>, ArgsError>
+    where E: GenericEvent, T: Any, F: FnMut(&T) -> U
+{
+    if e.event_id() != event_id {
+        return Ok(None);
+    }
+    e.with_args(|any| {
+        if let Some(args) = any.downcast_ref::<T>() {
+            Ok(Some(f(args)))
+        } else {
+            Err(ArgsError::new(event_id, expected))
+        }
+    })
+}
+
+/// Calls closure with the arguments of an event, if it has the id,
+/// also accepting arguments of an older type `L` that converts to `T`.
+///
+/// Used by events whose arguments were extended,
+/// so that `GenericEvent` implementations passing the old type keep working.
+pub fn try_with_legacy_args<E, T, L, U, F>(
+    e: &E,
+    event_id: EventId,
+    expected: &'static str,
+    mut f: F
+) -> Result<Option<U>, ArgsError>
+    where E: GenericEvent, T: Any + Clone + From<L>, L: Any + Clone, F: FnMut(T) -> U
+{
+    if e.event_id() != event_id {
+        return Ok(None);
+    }
+    e.with_args(|any| {
+        if let Some(args) = any.downcast_ref::<T>() {
+            Ok(Some(f(args.clone())))
+        } else if let Some(legacy) = any.downcast_ref::<L>() {
+            Ok(Some(f(T::from(legacy.clone()))))
+        } else {
+            Err(ArgsError::new(event_id, expected))
+        }
+    })
+}
 
 /// Implemented by all events
 pub trait GenericEvent: Sized {
@@ -19,18 +103,35 @@ pub trait GenericEvent: Sized {
     ;
     /// Converts from arguments to `Self`
     fn from_args(event_id: EventId, any: &Any, old_event: &Self) -> Option<Self>;
+    /// Converts from arguments to `Self`.
+    ///
+    /// Returns an error instead of panicking when the arguments have an unexpected type.
+    /// The default implementation calls `from_args`.
+    fn try_from_args(event_id: EventId, any: &Any, old_event: &Self)
+        -> Result<Option<Self>, ArgsError>
+    {
+        Ok(GenericEvent::from_args(event_id, any, old_event))
+    }
+    /// Returns the time the event happened in nanoseconds, if known.
+    ///
+    /// See [`Timestamped`](../struct.Timestamped.html).
+    fn timestamp(&self) -> Option<u64> { None }
 }
 
 impl GenericEvent for Input {
     fn event_id(&self) -> EventId {
         match self {
+            &Input::Composition(_) => COMPOSITION,
+            &Input::ControllerConnection(_) => CONTROLLER_CONNECTION,
             &Input::Cursor(_) => CURSOR,
+            &Input::FileDrag(_) => FILE_DRAG,
             &Input::Focus(_) => FOCUS,
-            &Input::Move(Motion::MouseCursor(_, _)) => MOUSE_CURSOR,
-            &Input::Move(Motion::MouseRelative(_, _)) => MOUSE_RELATIVE,
-            &Input::Move(Motion::MouseScroll(_, _)) => MOUSE_SCROLL,
+            &Input::Move(Motion::MouseCursor(_)) => MOUSE_CURSOR,
+            &Input::Move(Motion::MouseRelative(_)) => MOUSE_RELATIVE,
+            &Input::Move(Motion::MouseScroll(_)) => MOUSE_SCROLL,
             &Input::Move(Motion::ControllerAxis(_)) => CONTROLLER_AXIS,
             &Input::Move(Motion::Touch(_)) => TOUCH,
+            &Input::Move(Motion::Pen(_)) => PEN,
             &Input::Press(_) => PRESS,
             &Input::Release(_) => RELEASE,
             &Input::Resize(_, _) => RESIZE,
@@ -42,24 +143,32 @@ impl GenericEvent for Input {
         where F: FnMut(&Any) -> U
     {
         match self {
+            &Input::Composition(ref args) =>
+                f(args as &Any),
+            &Input::ControllerConnection(ref args) =>
+                f(args as &Any),
             &Input::Cursor(cursor) =>
                 f(&cursor as &Any),
+            &Input::FileDrag(ref args) =>
+                f(args as &Any),
             &Input::Focus(focused) =>
                 f(&focused as &Any),
             &Input::Move(Motion::ControllerAxis(args)) =>
                 f(&args as &Any),
-            &Input::Move(Motion::MouseCursor(x, y)) =>
-                f(&(x, y) as &Any),
-            &Input::Move(Motion::MouseRelative(x, y)) =>
-                f(&(x, y) as &Any),
-            &Input::Move(Motion::MouseScroll(x, y)) =>
-                f(&(x, y) as &Any),
+            &Input::Move(Motion::MouseCursor(args)) =>
+                f(&args as &Any),
+            &Input::Move(Motion::MouseRelative(args)) =>
+                f(&args as &Any),
+            &Input::Move(Motion::MouseScroll(args)) =>
+                f(&args as &Any),
             &Input::Move(Motion::Touch(args)) =>
                 f(&args as &Any),
-            &Input::Press(button) =>
-                f(&button as &Any),
-            &Input::Release(button) =>
-                f(&button as &Any),
+            &Input::Move(Motion::Pen(args)) =>
+                f(&args as &Any),
+            &Input::Press(args) =>
+                f(&args as &Any),
+            &Input::Release(args) =>
+                f(&args as &Any),
             &Input::Resize(w, h) =>
                 f(&(w, h) as &Any),
             &Input::Text(ref text) =>
@@ -67,87 +176,131 @@ impl GenericEvent for Input {
         }
     }
 
-    fn from_args(event_id: EventId, any: &Any, _old_event: &Self) -> Option<Self> {
-        match event_id {
+    fn from_args(event_id: EventId, any: &Any, old_event: &Self) -> Option<Self> {
+        Input::try_from_args(event_id, any, old_event).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_from_args(event_id: EventId, any: &Any, _old_event: &Self)
+        -> Result<Option<Self>, ArgsError>
+    {
+        Ok(match event_id {
+            x if x == COMPOSITION => {
+                if let Some(args) = any.downcast_ref::<Composition>() {
+                    Some(Input::Composition(args.clone()))
+                } else {
+                    return Err(ArgsError::new(event_id, "Composition"))
+                }
+            }
             x if x == CONTROLLER_AXIS => {
                 if let Some(&args) = any.downcast_ref::<ControllerAxisArgs>() {
                     Some(Input::Move(Motion::ControllerAxis(args)))
                 } else {
-                    panic!("Expected ControllerAxisArgs")
+                    return Err(ArgsError::new(event_id, "ControllerAxisArgs"))
+                }
+            }
+            x if x == CONTROLLER_CONNECTION => {
+                if let Some(args) = any.downcast_ref::<ControllerConnection>() {
+                    Some(Input::ControllerConnection(args.clone()))
+                } else {
+                    return Err(ArgsError::new(event_id, "ControllerConnection"))
                 }
             }
             x if x == CURSOR => {
                 if let Some(&cursor) = any.downcast_ref::<bool>() {
                     Some(Input::Cursor(cursor))
                 } else {
-                    panic!("Expected bool")
+                    return Err(ArgsError::new(event_id, "bool"))
+                }
+            }
+            x if x == FILE_DRAG => {
+                if let Some(args) = any.downcast_ref::<FileDrag>() {
+                    Some(Input::FileDrag(args.clone()))
+                } else {
+                    return Err(ArgsError::new(event_id, "FileDrag"))
                 }
             }
             x if x == FOCUS => {
                 if let Some(&focused) = any.downcast_ref::<bool>() {
                     Some(Input::Focus(focused))
                 } else {
-                    panic!("Expected bool")
+                    return Err(ArgsError::new(event_id, "bool"))
                 }
             }
             x if x == MOUSE_CURSOR => {
-                if let Some(&(x, y)) = any.downcast_ref::<(f64, f64)>() {
-                    Some(Input::Move(Motion::MouseCursor(x, y)))
+                if let Some(&args) = any.downcast_ref::<MouseCursorArgs>() {
+                    Some(Input::Move(Motion::MouseCursor(args)))
+                } else if let Some(&xy) = any.downcast_ref::<(f64, f64)>() {
+                    Some(Input::Move(Motion::MouseCursor(xy.into())))
                 } else {
-                    panic!("Expected (f64, f64)")
+                    return Err(ArgsError::new(event_id, "MouseCursorArgs"))
                 }
             }
             x if x == MOUSE_RELATIVE => {
-                if let Some(&(x, y)) = any.downcast_ref::<(f64, f64)>() {
-                    Some(Input::Move(Motion::MouseRelative(x, y)))
+                if let Some(&args) = any.downcast_ref::<MouseRelativeArgs>() {
+                    Some(Input::Move(Motion::MouseRelative(args)))
+                } else if let Some(&xy) = any.downcast_ref::<(f64, f64)>() {
+                    Some(Input::Move(Motion::MouseRelative(xy.into())))
                 } else {
-                    panic!("Expected (f64, f64)")
+                    return Err(ArgsError::new(event_id, "MouseRelativeArgs"))
                 }
             }
             x if x == MOUSE_SCROLL => {
-                if let Some(&(x, y)) = any.downcast_ref::<(f64, f64)>() {
-                    Some(Input::Move(Motion::MouseScroll(x, y)))
+                if let Some(&args) = any.downcast_ref::<ScrollArgs>() {
+                    Some(Input::Move(Motion::MouseScroll(args)))
+                } else if let Some(&xy) = any.downcast_ref::<(f64, f64)>() {
+                    Some(Input::Move(Motion::MouseScroll(xy.into())))
+                } else {
+                    return Err(ArgsError::new(event_id, "ScrollArgs"))
+                }
+            }
+            x if x == PEN => {
+                if let Some(&args) = any.downcast_ref::<PenArgs>() {
+                    Some(Input::Move(Motion::Pen(args)))
                 } else {
-                    panic!("Expected (f64, f64)")
+                    return Err(ArgsError::new(event_id, "PenArgs"))
                 }
             }
             x if x == PRESS => {
-                if let Some(&button) = any.downcast_ref::<Button>() {
-                    Some(Input::Press(button))
+                if let Some(&args) = any.downcast_ref::<ButtonArgs>() {
+                    Some(Input::Press(args))
+                } else if let Some(&button) = any.downcast_ref::<Button>() {
+                    Some(Input::Press(button.into()))
                 } else {
-                    panic!("Expected Button")
+                    return Err(ArgsError::new(event_id, "ButtonArgs"))
                 }
             }
             x if x == RELEASE => {
-                if let Some(&button) = any.downcast_ref::<Button>() {
-                    Some(Input::Release(button))
+                if let Some(&args) = any.downcast_ref::<ButtonArgs>() {
+                    Some(Input::Release(args))
+                } else if let Some(&button) = any.downcast_ref::<Button>() {
+                    Some(Input::Release(button.into()))
                 } else {
-                    panic!("Expected Button")
+                    return Err(ArgsError::new(event_id, "ButtonArgs"))
                 }
             }
             x if x == RESIZE => {
                 if let Some(&(w, h)) = any.downcast_ref::<(u32, u32)>() {
                     Some(Input::Resize(w as u32, h as u32))
                 } else {
-                    panic!("Expected (u32, u32))")
+                    return Err(ArgsError::new(event_id, "(u32, u32)"))
                 }
             }
             x if x == TEXT => {
                 if let Some(text) = any.downcast_ref::<String>() {
                     Some(Input::Text(text.to_owned()))
                 } else {
-                    panic!("Expected &str")
+                    return Err(ArgsError::new(event_id, "String"))
                 }
             }
             x if x == TOUCH => {
                 if let Some(&args) = any.downcast_ref::<TouchArgs>() {
                     Some(Input::Move(Motion::Touch(args)))
                 } else {
-                    panic!("Expected TouchArgs")
+                    return Err(ArgsError::new(event_id, "TouchArgs"))
                 }
             }
-            _ => { return None; }
-        }
+            _ => None
+        })
     }
 }
 
@@ -161,6 +314,7 @@ impl<I: GenericEvent> GenericEvent for Event<I> {
             &Event::Input(ref input) => {
                 input.event_id()
             }
+            &Event::Custom(event_id, _) => event_id,
         }
     }
 
@@ -183,49 +337,217 @@ impl<I: GenericEvent> GenericEvent for Event<I> {
             &Event::Input(ref input) => {
                 input.with_args(f)
             }
+            &Event::Custom(_, ref args) => {
+                f(args as &Any)
+            }
+        }
+    }
+
+    fn timestamp(&self) -> Option<u64> {
+        match self {
+            &Event::Input(ref input) => input.timestamp(),
+            _ => None
         }
     }
 
     fn from_args(event_id: EventId, any: &Any, old_event: &Self) -> Option<Self> {
-        match event_id {
+        Event::try_from_args(event_id, any, old_event).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_from_args(event_id: EventId, any: &Any, old_event: &Self)
+        -> Result<Option<Self>, ArgsError>
+    {
+        Ok(match event_id {
             x if x == UPDATE => {
                 if let Some(&args) = any.downcast_ref::<UpdateArgs>() {
                     Some(Event::Update(args))
                 } else {
-                    panic!("Expected UpdateArgs")
+                    return Err(ArgsError::new(event_id, "UpdateArgs"))
                 }
             }
             x if x == RENDER => {
                 if let Some(&args) = any.downcast_ref::<RenderArgs>() {
                     Some(Event::Render(args))
                 } else {
-                    panic!("Expected RenderArgs")
+                    return Err(ArgsError::new(event_id, "RenderArgs"))
                 }
             }
             x if x == AFTER_RENDER => {
                 if let Some(&args) = any.downcast_ref::<AfterRenderArgs>() {
                     Some(Event::AfterRender(args))
                 } else {
-                    panic!("Expected AfterRenderArgs")
+                    return Err(ArgsError::new(event_id, "AfterRenderArgs"))
                 }
             }
             x if x == IDLE => {
                 if let Some(&args) = any.downcast_ref::<IdleArgs>() {
                     Some(Event::Idle(args))
                 } else {
-                    panic!("Expected IdleArgs")
+                    return Err(ArgsError::new(event_id, "IdleArgs"))
                 }
             }
             _ => {
+                if let Some(args) = any.downcast_ref::<CustomArgs>() {
+                    return Ok(Some(Event::Custom(event_id, args.clone())));
+                }
                 if let &Event::Input(ref old_input) = old_event {
                     let input: Option<I> =
-                        GenericEvent::from_args(event_id, any, old_input);
-                    match input {
-                        Some(x) => Some(Event::Input(x)),
-                        None => None
-                    }
-                } else { return None; }
+                        try!(GenericEvent::try_from_args(event_id, any, old_input));
+                    input.map(|x| Event::Input(x))
+                } else { None }
             }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+
+    // An event that passes arguments of the wrong type.
+    #[derive(Clone, Debug)]
+    struct BadEvent(EventId);
+
+    impl GenericEvent for BadEvent {
+        fn event_id(&self) -> EventId { self.0 }
+
+        fn with_args<'a, F, U>(&'a self, mut f: F) -> U
+            where F: FnMut(&Any) -> U
+        {
+            f(&true as &Any)
+        }
+
+        fn from_args(_event_id: EventId, _any: &Any, _old_event: &Self) -> Option<Self> {
+            None
         }
     }
+
+    // An event that passes arguments of an older type.
+    #[derive(Clone, Debug)]
+    struct LegacyEvent<T>(EventId, T);
+
+    impl<T: Any + Clone> GenericEvent for LegacyEvent<T> {
+        fn event_id(&self) -> EventId { self.0 }
+
+        fn with_args<'a, F, U>(&'a self, mut f: F) -> U
+            where F: FnMut(&Any) -> U
+        {
+            f(&self.1 as &Any)
+        }
+
+        fn from_args(event_id: EventId, any: &Any, _old_event: &Self) -> Option<Self> {
+            any.downcast_ref::<T>().map(|args| LegacyEvent(event_id, args.clone()))
+        }
+    }
+
+    #[test]
+    fn test_legacy_args() {
+        use { Button, ButtonArgs, Key, MouseCursorEvent, MouseRelativeEvent, MouseScrollEvent,
+              PressEvent, ReleaseEvent, ScrollUnit };
+
+        let button = Button::Keyboard(Key::A);
+        let e = LegacyEvent(PRESS, button);
+        assert_eq!(e.press_args(), Some(button));
+        assert_eq!(e.try_press_button_args(), Ok(Some(ButtonArgs::from(button))));
+        assert_eq!(LegacyEvent(RELEASE, button).release_button_args(),
+                   Some(ButtonArgs::from(button)));
+        // Events without extra arguments are created with the legacy type.
+        let other = Button::Keyboard(Key::B);
+        let x: Option<LegacyEvent<Button>> = PressEvent::from_button(other, &e);
+        assert_eq!(x.unwrap().press_args(), Some(other));
+        assert!(ReleaseEvent::from_button(button, &e).is_some());
+        assert!(PressEvent::from_button_args(ButtonArgs::new(button).device(1), &e).is_none());
+        let e = LegacyEvent(MOUSE_SCROLL, (1.0, 2.0));
+        assert_eq!(e.mouse_scroll_args(), Some([1.0, 2.0]));
+        assert_eq!(e.try_mouse_scroll_full_args(), Ok(Some(ScrollArgs::new(1.0, 2.0))));
+        let x: Option<LegacyEvent<(f64, f64)>> = MouseScrollEvent::from_xy(3.0, 4.0, &e);
+        assert_eq!(x.unwrap().mouse_scroll_args(), Some([3.0, 4.0]));
+        assert!(MouseScrollEvent::from_scroll_args(
+            ScrollArgs::new(3.0, 4.0).unit(ScrollUnit::Pixels), &e).is_none());
+        let e = LegacyEvent(MOUSE_CURSOR, (1.0, 2.0));
+        assert_eq!(e.mouse_cursor_args(), Some([1.0, 2.0]));
+        assert_eq!(e.try_mouse_cursor_full_args(), Ok(Some(MouseCursorArgs::new(1.0, 2.0))));
+        let x: Option<LegacyEvent<(f64, f64)>> = MouseCursorEvent::from_xy(3.0, 4.0, &e);
+        assert_eq!(x.unwrap().mouse_cursor_args(), Some([3.0, 4.0]));
+        assert!(MouseCursorEvent::from_mouse_cursor_args(
+            MouseCursorArgs::new(3.0, 4.0).device(1), &e).is_none());
+        let e = LegacyEvent(MOUSE_RELATIVE, (1.0, 2.0));
+        assert_eq!(e.mouse_relative_args(), Some([1.0, 2.0]));
+        assert_eq!(e.try_mouse_relative_full_args(), Ok(Some(MouseRelativeArgs::new(1.0, 2.0))));
+        let x: Option<LegacyEvent<(f64, f64)>> = MouseRelativeEvent::from_xy(3.0, 4.0, &e);
+        assert_eq!(x.unwrap().mouse_relative_args(), Some([3.0, 4.0]));
+        assert!(MouseRelativeEvent::from_mouse_relative_args(
+            MouseRelativeArgs::new(3.0, 4.0).device(1), &e).is_none());
+    }
+
+    #[test]
+    fn test_args_error() {
+        use { PressEvent, RenderEvent, UpdateEvent, FocusEvent };
+
+        let e = BadEvent(RENDER);
+        assert_eq!(e.try_render_args(), Err(ArgsError::new(RENDER, "RenderArgs")));
+        assert_eq!(e.try_update_args(), Ok(None));
+        assert_eq!(BadEvent(FOCUS).try_focus_args(), Ok(Some(true)));
+        assert_eq!(BadEvent(PRESS).try_press_button_args(),
+                   Err(ArgsError::new(PRESS, "ButtonArgs")));
+        assert_eq!(format!("{}", ArgsError::new(RENDER, "RenderArgs")),
+                   "Expected RenderArgs for event `piston/render`");
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected RenderArgs for event `piston/render`")]
+    fn test_args_error_panic() {
+        use RenderEvent;
+
+        BadEvent(RENDER).render_args();
+    }
+
+    #[test]
+    fn test_args_error_ignored() {
+        use { ActionMap, AxisFilter, ClickTracker, Dispatcher, DragTracker, GestureRecognizer,
+              InputState, KeyRepeat, TextInput };
+        use { COMPOSITION, CONTROLLER_AXIS, CONTROLLER_CONNECTION, TEXT, TOUCH };
+
+        // Events with arguments of the wrong type are skipped instead of panicking.
+        let mut state = InputState::new();
+        let mut actions = ActionMap::new(vec![]);
+        let mut axes = AxisFilter::default();
+        let mut clicks = ClickTracker::default();
+        let mut dispatcher = Dispatcher::new();
+        let mut drags = DragTracker::default();
+        let mut gestures = GestureRecognizer::default();
+        let mut repeat = KeyRepeat::new();
+        let mut text = TextInput::new();
+        for &id in &[COMPOSITION, CONTROLLER_AXIS, CONTROLLER_CONNECTION, MOUSE_CURSOR,
+                     MOUSE_RELATIVE, MOUSE_SCROLL, PEN, PRESS, RELEASE, RESIZE, TEXT, TOUCH,
+                     UPDATE] {
+            let e = BadEvent(id);
+            state.event(&e);
+            actions.event(&e, |_| {});
+            assert!(axes.event(&e).is_none());
+            clicks.event(&e, |_| {});
+            dispatcher.event(&e, |_, _, _| false);
+            drags.event(&e, |_| {});
+            gestures.event(&e, |_| {});
+            repeat.event(&e, |_| {});
+            assert!(!text.event(&e));
+        }
+    }
+
+    #[test]
+    fn test_try_from_args() {
+        use { Button, Key, UpdateArgs };
+
+        let old = Input::Press(Button::Keyboard(Key::A).into());
+        assert_eq!(Input::try_from_args(PRESS, &true as &Any, &old),
+                   Err(ArgsError::new(PRESS, "ButtonArgs")));
+        let old: Event = Event::Input(old);
+        assert_eq!(Event::try_from_args(UPDATE, &true as &Any, &old),
+                   Err(ArgsError::new(UPDATE, "UpdateArgs")));
+        assert_eq!(Event::try_from_args(PRESS, &true as &Any, &old),
+                   Err(ArgsError::new(PRESS, "ButtonArgs")));
+        assert_eq!(Event::try_from_args(UPDATE, &UpdateArgs { dt: 1.0 } as &Any, &old),
+                   Ok(Some(Event::Update(UpdateArgs { dt: 1.0 }))));
+    }
 }
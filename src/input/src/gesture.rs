@@ -0,0 +1,459 @@
+//! Recognizes touch gestures.
+//!
+//! Touch positions are normalized 0..1, so distances and velocities
+//! in the settings are in normalized units.
+//! Time is measured by summing the delta time of update events.
+
+use std::default::Default;
+use std::f64::consts::PI;
+
+use { GenericEvent, Touch, TouchArgs };
+
+/// The direction of a swipe.
+///
+/// The y axis points down, as in window coordinates.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SwipeDirection {
+    /// Swiped to the left.
+    Left,
+    /// Swiped to the right.
+    Right,
+    /// Swiped up.
+    Up,
+    /// Swiped down.
+    Down,
+}
+
+/// A recognized touch gesture.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Gesture {
+    /// A short touch without moving.
+    Tap([f64; 2]),
+    /// A second tap shortly after and close to the first one.
+    DoubleTap([f64; 2]),
+    /// A touch held down without moving.
+    LongPress([f64; 2]),
+    /// A quick movement of one finger.
+    Swipe {
+        /// The main direction of the swipe.
+        direction: SwipeDirection,
+        /// The average velocity in units per second.
+        velocity: [f64; 2],
+    },
+    /// Two fingers moved closer together or further apart.
+    Pinch {
+        /// The point between the fingers.
+        center: [f64; 2],
+        /// The distance between the fingers relative to when the second touched.
+        scale: f64,
+    },
+    /// Two fingers rotated around each other.
+    Rotate {
+        /// The point between the fingers.
+        center: [f64; 2],
+        /// The angle in radians relative to when the second finger touched.
+        angle: f64,
+    },
+}
+
+/// Thresholds used to recognize gestures.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GestureSettings {
+    /// How far a touch can move and still count as not moving.
+    pub tap_distance: f64,
+    /// The maximum duration of a tap in seconds.
+    pub tap_time: f64,
+    /// The maximum time between two taps of a double tap in seconds.
+    pub double_tap_time: f64,
+    /// The minimum duration of a long press in seconds.
+    pub long_press_time: f64,
+    /// The minimum speed of a swipe in units per second.
+    pub swipe_speed: f64,
+}
+
+impl Default for GestureSettings {
+    fn default() -> GestureSettings {
+        GestureSettings {
+            tap_distance: 0.02,
+            tap_time: 0.3,
+            double_tap_time: 0.3,
+            long_press_time: 0.5,
+            swipe_speed: 0.5,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct TouchPoint {
+    device: i64,
+    id: i64,
+    start: [f64; 2],
+    start_time: f64,
+    pos: [f64; 2],
+    moved: bool,
+    long_pressed: bool,
+}
+
+// The two touches used for pinch and rotate,
+// with their distance and angle when the second touched.
+#[derive(Copy, Clone, Debug)]
+struct TwoFingers {
+    a: (i64, i64),
+    b: (i64, i64),
+    distance: f64,
+    angle: f64,
+}
+
+/// Turns touch events into gestures.
+#[derive(Clone, Debug)]
+pub struct GestureRecognizer {
+    /// The thresholds used to recognize gestures.
+    pub settings: GestureSettings,
+    time: f64,
+    touches: Vec<TouchPoint>,
+    last_tap: Option<(f64, [f64; 2])>,
+    // Set when more than one finger touched since all fingers were lifted.
+    multi_touch: bool,
+    two_fingers: Option<TwoFingers>,
+}
+
+impl GestureRecognizer {
+    /// Creates a new gesture recognizer.
+    pub fn new(settings: GestureSettings) -> GestureRecognizer {
+        GestureRecognizer {
+            settings: settings,
+            time: 0.0,
+            touches: vec![],
+            last_tap: None,
+            multi_touch: false,
+            two_fingers: None,
+        }
+    }
+
+    /// Change gesture state depending on event.
+    ///
+    /// Calls closure for every recognized gesture.
+    /// When the window loses focus, all touches are cancelled.
+    /// Events with arguments of an unexpected type are ignored.
+    pub fn event<E, F>(&mut self, e: &E, mut f: F)
+        where E: GenericEvent, F: FnMut(Gesture)
+    {
+        use { FocusEvent, TouchEvent, UpdateEvent };
+
+        if let Ok(Some(args)) = e.try_touch_args() {
+            match args.touch {
+                Touch::Start => self.start(&args),
+                Touch::Move => self.moved(&args, &mut f),
+                Touch::End => self.end(&args, &mut f),
+                Touch::Cancel => self.cancel(&args),
+            }
+        }
+        if let Ok(Some(args)) = e.try_update_args() {
+            self.time += args.dt;
+            if !self.multi_touch {
+                let time = self.time;
+                let long_press_time = self.settings.long_press_time;
+                for t in &mut self.touches {
+                    if !t.moved && !t.long_pressed &&
+                       time - t.start_time >= long_press_time {
+                        t.long_pressed = true;
+                        f(Gesture::LongPress(t.pos));
+                    }
+                }
+            }
+        }
+        if let Ok(Some(false)) = e.try_focus_args() {
+            self.touches.clear();
+            self.reset();
+        }
+    }
+
+    fn find(&self, args: &TouchArgs) -> Option<usize> {
+        self.touches.iter().position(|t|
+            t.device == args.device && t.id == args.id)
+    }
+
+    fn reset(&mut self) {
+        self.multi_touch = false;
+        self.two_fingers = None;
+    }
+
+    fn start(&mut self, args: &TouchArgs) {
+        let pos = args.position();
+        self.touches.push(TouchPoint {
+            device: args.device,
+            id: args.id,
+            start: pos,
+            start_time: self.time,
+            pos: pos,
+            moved: false,
+            long_pressed: false,
+        });
+        if self.touches.len() > 1 {
+            self.multi_touch = true;
+        }
+        if self.two_fingers.is_none() {
+            self.start_two_fingers();
+        }
+    }
+
+    // Uses the first two touches for pinch and rotate, starting at their current positions.
+    fn start_two_fingers(&mut self) {
+        self.two_fingers = if self.touches.len() >= 2 {
+            let (a, b) = (&self.touches[0], &self.touches[1]);
+            Some(TwoFingers {
+                a: (a.device, a.id),
+                b: (b.device, b.id),
+                distance: distance(a.pos, b.pos),
+                angle: angle(a.pos, b.pos),
+            })
+        } else {
+            None
+        };
+    }
+
+    fn position(&self, (device, id): (i64, i64)) -> Option<[f64; 2]> {
+        self.touches.iter().find(|t| t.device == device && t.id == id).map(|t| t.pos)
+    }
+
+    fn moved<F>(&mut self, args: &TouchArgs, f: &mut F)
+        where F: FnMut(Gesture)
+    {
+        let i = match self.find(args) {
+            Some(i) => i,
+            None => { return; }
+        };
+        let tap_distance = self.settings.tap_distance;
+        {
+            let t = &mut self.touches[i];
+            t.pos = args.position();
+            if distance(t.start, t.pos) > tap_distance {
+                t.moved = true;
+            }
+        }
+        if let Some(start) = self.two_fingers {
+            // Only the two fingers of the baseline are used for pinch and rotate.
+            let id = (args.device, args.id);
+            if id != start.a && id != start.b { return; }
+            let (a, b) = match (self.position(start.a), self.position(start.b)) {
+                (Some(a), Some(b)) => (a, b),
+                _ => return,
+            };
+            let center = [(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0];
+            if start.distance > 0.0 {
+                f(Gesture::Pinch {
+                    center: center,
+                    scale: distance(a, b) / start.distance,
+                });
+            }
+            let mut diff = angle(a, b) - start.angle;
+            while diff > PI { diff -= 2.0 * PI; }
+            while diff <= -PI { diff += 2.0 * PI; }
+            f(Gesture::Rotate {
+                center: center,
+                angle: diff,
+            });
+        }
+    }
+
+    fn end<F>(&mut self, args: &TouchArgs, f: &mut F)
+        where F: FnMut(Gesture)
+    {
+        let i = match self.find(args) {
+            Some(i) => i,
+            None => { return; }
+        };
+        let mut t = self.touches.remove(i);
+        self.after_remove(&t);
+        t.pos = args.position();
+        if distance(t.start, t.pos) > self.settings.tap_distance {
+            t.moved = true;
+        }
+        let duration = self.time - t.start_time;
+        if !self.multi_touch && !t.long_pressed {
+            if !t.moved && duration <= self.settings.tap_time {
+                self.tap(t.pos, f);
+            } else if t.moved && duration > 0.0 {
+                let velocity = [
+                    (t.pos[0] - t.start[0]) / duration,
+                    (t.pos[1] - t.start[1]) / duration,
+                ];
+                let speed = (velocity[0] * velocity[0] +
+                             velocity[1] * velocity[1]).sqrt();
+                if speed >= self.settings.swipe_speed {
+                    let direction = if velocity[0].abs() > velocity[1].abs() {
+                        if velocity[0] < 0.0 { SwipeDirection::Left }
+                        else { SwipeDirection::Right }
+                    } else {
+                        if velocity[1] < 0.0 { SwipeDirection::Up }
+                        else { SwipeDirection::Down }
+                    };
+                    f(Gesture::Swipe {
+                        direction: direction,
+                        velocity: velocity,
+                    });
+                }
+            }
+        }
+        if self.touches.is_empty() {
+            self.reset();
+        }
+    }
+
+    fn tap<F>(&mut self, pos: [f64; 2], f: &mut F)
+        where F: FnMut(Gesture)
+    {
+        if let Some((time, last_pos)) = self.last_tap {
+            if self.time - time <= self.settings.double_tap_time &&
+               distance(pos, last_pos) <= self.settings.tap_distance {
+                self.last_tap = None;
+                f(Gesture::DoubleTap(pos));
+                return;
+            }
+        }
+        self.last_tap = Some((self.time, pos));
+        f(Gesture::Tap(pos));
+    }
+
+    fn cancel(&mut self, args: &TouchArgs) {
+        if let Some(i) = self.find(args) {
+            let t = self.touches.remove(i);
+            self.after_remove(&t);
+            if self.touches.is_empty() {
+                self.reset();
+            }
+        }
+    }
+
+    // Starts a new baseline when a finger of the current baseline is removed.
+    fn after_remove(&mut self, t: &TouchPoint) {
+        if let Some(two) = self.two_fingers {
+            let id = (t.device, t.id);
+            if id == two.a || id == two.b {
+                self.start_two_fingers();
+            }
+        }
+    }
+}
+
+impl Default for GestureRecognizer {
+    fn default() -> GestureRecognizer {
+        GestureRecognizer::new(Default::default())
+    }
+}
+
+fn distance(a: [f64; 2], b: [f64; 2]) -> f64 {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    (dx * dx + dy * dy).sqrt()
+}
+
+fn angle(a: [f64; 2], b: [f64; 2]) -> f64 {
+    (b[1] - a[1]).atan2(b[0] - a[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use { Event, Input, Motion, Touch, TouchArgs, UpdateArgs };
+
+    fn touch(id: i64, pos: [f64; 2], touch: Touch) -> Event {
+        Event::Input(Input::Move(Motion::Touch(
+            TouchArgs::new(0, id, pos, 1.0, touch))))
+    }
+
+    fn update(dt: f64) -> Event {
+        Event::Update(UpdateArgs { dt: dt })
+    }
+
+    #[test]
+    fn test_tap_and_double_tap() {
+        let mut g = GestureRecognizer::default();
+        let mut gestures = vec![];
+        let pos = [0.5, 0.5];
+        for e in &[touch(0, pos, Touch::Start), update(0.1), touch(0, pos, Touch::End),
+                   update(0.1), touch(0, pos, Touch::Start), touch(0, pos, Touch::End),
+                   update(1.0), touch(0, pos, Touch::Start), touch(0, pos, Touch::End)] {
+            g.event(e, |x| gestures.push(x));
+        }
+        assert_eq!(gestures, vec![
+            Gesture::Tap(pos),
+            Gesture::DoubleTap(pos),
+            Gesture::Tap(pos),
+        ]);
+    }
+
+    #[test]
+    fn test_long_press() {
+        let mut g = GestureRecognizer::default();
+        let mut gestures = vec![];
+        let pos = [0.5, 0.5];
+        for e in &[touch(0, pos, Touch::Start), update(0.4), update(0.4),
+                   update(0.4), touch(0, pos, Touch::End)] {
+            g.event(e, |x| gestures.push(x));
+        }
+        assert_eq!(gestures, vec![Gesture::LongPress(pos)]);
+    }
+
+    #[test]
+    fn test_swipe() {
+        let mut g = GestureRecognizer::default();
+        let mut gestures = vec![];
+        for e in &[touch(0, [0.8, 0.5], Touch::Start), update(0.1),
+                   touch(0, [0.5, 0.5], Touch::Move), update(0.1),
+                   touch(0, [0.2, 0.5], Touch::End)] {
+            g.event(e, |x| gestures.push(x));
+        }
+        assert_eq!(gestures.len(), 1);
+        match gestures[0] {
+            Gesture::Swipe { direction, velocity } => {
+                assert_eq!(direction, SwipeDirection::Left);
+                assert!((velocity[0] + 3.0).abs() < 1e-6);
+            }
+            _ => panic!("Expected swipe"),
+        }
+    }
+
+    #[test]
+    fn test_pinch_and_rotate() {
+        let mut g = GestureRecognizer::default();
+        let mut gestures = vec![];
+        for e in &[touch(0, [0.4, 0.5], Touch::Start),
+                   touch(1, [0.6, 0.5], Touch::Start),
+                   touch(1, [0.5, 0.6], Touch::Move),
+                   touch(1, [0.5, 0.6], Touch::End),
+                   touch(0, [0.4, 0.5], Touch::End)] {
+            g.event(e, |x| gestures.push(x));
+        }
+        assert_eq!(gestures.len(), 2);
+        match gestures[0] {
+            Gesture::Pinch { scale, .. } => assert!((scale - 0.5f64.sqrt()).abs() < 1e-6),
+            _ => panic!("Expected pinch"),
+        }
+        match gestures[1] {
+            Gesture::Rotate { angle, .. } => assert!((angle - PI / 4.0).abs() < 1e-6),
+            _ => panic!("Expected rotate"),
+        }
+    }
+
+    #[test]
+    fn test_pinch_after_lifting_first_finger() {
+        let mut g = GestureRecognizer::default();
+        let mut gestures = vec![];
+        for e in &[touch(0, [0.4, 0.5], Touch::Start),
+                   touch(1, [0.6, 0.5], Touch::Start),
+                   touch(2, [0.5, 0.9], Touch::Start),
+                   touch(0, [0.4, 0.5], Touch::End),
+                   touch(2, [0.5, 0.9], Touch::Move)] {
+            g.event(e, |x| gestures.push(x));
+        }
+        assert_eq!(gestures.len(), 2);
+        match gestures[0] {
+            Gesture::Pinch { scale, .. } => assert!((scale - 1.0).abs() < 1e-6),
+            _ => panic!("Expected pinch"),
+        }
+        match gestures[1] {
+            Gesture::Rotate { angle, .. } => assert!(angle.abs() < 1e-6),
+            _ => panic!("Expected rotate"),
+        }
+    }
+}
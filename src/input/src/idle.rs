@@ -1,6 +1,7 @@
 use std::any::Any;
 
-use { GenericEvent, IDLE };
+use { ArgsError, GenericEvent, IDLE };
+use generic_event::try_with_args;
 
 /// Idle arguments, such as expected idle time in seconds.
 #[derive(Copy, Clone, PartialEq, Debug)]
@@ -24,6 +25,8 @@ pub trait IdleEvent: Sized {
     fn idle_args(&self) -> Option<IdleArgs> {
         self.idle(|args| args.clone())
     }
+    /// Returns idle arguments, or an error if they have an unexpected type.
+    fn try_idle_args(&self) -> Result<Option<IdleArgs>, ArgsError>;
 }
 
 impl<T> IdleEvent for T where T: GenericEvent {
@@ -31,19 +34,15 @@ impl<T> IdleEvent for T where T: GenericEvent {
         GenericEvent::from_args(IDLE, args as &Any, old_event)
     }
 
-    fn idle<U, F>(&self, mut f: F) -> Option<U>
+    fn idle<U, F>(&self, f: F) -> Option<U>
         where F: FnMut(&IdleArgs) -> U
     {
-        if self.event_id() != IDLE {
-            return None;
-        }
-        self.with_args(|any| {
-            if let Some(args) = any.downcast_ref::<IdleArgs>() {
-                Some(f(args))
-            } else {
-                panic!("Expected IdleArgs")
-            }
-        })
+        try_with_args(self, IDLE, "IdleArgs", f)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_idle_args(&self) -> Result<Option<IdleArgs>, ArgsError> {
+        try_with_args(self, IDLE, "IdleArgs", |&args: &IdleArgs| args)
     }
 }
 
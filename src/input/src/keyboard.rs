@@ -2,10 +2,37 @@
 
 use std::default::Default;
 use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
 
 use GenericEvent;
 use Button;
 
+// Implements `Display` and `FromStr` for an enum without fields,
+// from one list of variants that are named as in the source.
+macro_rules! names {
+    ($ty:ident, $err:tt, $($variant:ident,)*) => {
+        impl fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(match *self {
+                    $($ty::$variant => stringify!($variant),)*
+                })
+            }
+        }
+
+        impl FromStr for $ty {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<$ty, String> {
+                Ok(match s {
+                    $(stringify!($variant) => $ty::$variant,)*
+                    _ => { return Err(format!($err, s)); }
+                })
+            }
+        }
+    }
+}
+
 // Defining every combination to allow assignment in static expressions.
 bitflags!(
     #[allow(missing_docs)]
@@ -63,11 +90,12 @@ impl ModifierKey {
     /// Change modifier key state depending on input.
     ///
     /// If the left or side button is released, it counts as a release.
+    /// Events with arguments of an unexpected type are ignored.
     pub fn event<E: GenericEvent>(&mut self, e: &E) {
         use { FocusEvent, PressEvent, ReleaseEvent };
 
-        if let Some(button) = e.press_args() {
-            match button {
+        if let Ok(Some(args)) = e.try_press_button_args() {
+            match args.button {
                 Button::Keyboard(Key::LCtrl) |
                 Button::Keyboard(Key::RCtrl) => self.insert(CTRL),
                 Button::Keyboard(Key::LShift) |
@@ -79,8 +107,8 @@ impl ModifierKey {
                 _ => {}
             }
         }
-        if let Some(button) = e.release_args() {
-            match button {
+        if let Ok(Some(args)) = e.try_release_button_args() {
+            match args.button {
                 Button::Keyboard(Key::LCtrl) |
                 Button::Keyboard(Key::RCtrl) => self.remove(CTRL),
                 Button::Keyboard(Key::LShift) |
@@ -92,7 +120,7 @@ impl ModifierKey {
                 _ => {}
             }
         }
-        if let Some(false) = e.focus_args() {
+        if let Ok(Some(false)) = e.try_focus_args() {
             *self = NO_MODIFIER;
         }
     }
@@ -102,6 +130,43 @@ impl Default for ModifierKey {
     fn default() -> ModifierKey { NO_MODIFIER }
 }
 
+/// Formats modifier keys joined with `+`, for example `Ctrl+Shift`.
+impl fmt::Display for ModifierKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names = [(CTRL, "Ctrl"), (SHIFT, "Shift"), (ALT, "Alt"), (GUI, "Gui")];
+        let mut first = true;
+        for &(modifier, name) in &names {
+            if !self.contains(modifier) { continue; }
+            if !first { try!(write!(f, "+")); }
+            try!(write!(f, "{}", name));
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+/// Parses modifier keys joined with `+`, for example `Ctrl+Shift`.
+///
+/// An empty string means no modifier.
+impl FromStr for ModifierKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ModifierKey, String> {
+        let mut res = NO_MODIFIER;
+        if s.len() == 0 { return Ok(res); }
+        for name in s.split('+') {
+            res.insert(match name {
+                "Ctrl" => CTRL,
+                "Shift" => SHIFT,
+                "Alt" => ALT,
+                "Gui" => GUI,
+                _ => { return Err(format!("Unknown modifier key `{}`", name)); }
+            });
+        }
+        Ok(res)
+    }
+}
+
 /// Represent a keyboard key.
 /// Keycodes follows SDL http://wiki.libsdl.org/SDLKeycodeLookup
 #[allow(missing_docs)]
@@ -627,6 +692,854 @@ impl From<Key> for u32 {
     }
 }
 
+names! { Key, "Unknown key `{}`",
+    Unknown,
+    Backspace,
+    Tab,
+    Return,
+    Escape,
+    Space,
+    Exclaim,
+    Quotedbl,
+    Hash,
+    Dollar,
+    Percent,
+    Ampersand,
+    Quote,
+    LeftParen,
+    RightParen,
+    Asterisk,
+    Plus,
+    Comma,
+    Minus,
+    Period,
+    Slash,
+    D0,
+    D1,
+    D2,
+    D3,
+    D4,
+    D5,
+    D6,
+    D7,
+    D8,
+    D9,
+    Colon,
+    Semicolon,
+    Less,
+    Equals,
+    Greater,
+    Question,
+    At,
+    LeftBracket,
+    Backslash,
+    RightBracket,
+    Caret,
+    Underscore,
+    Backquote,
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Delete,
+    CapsLock,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    PrintScreen,
+    ScrollLock,
+    Pause,
+    Insert,
+    Home,
+    PageUp,
+    End,
+    PageDown,
+    Right,
+    Left,
+    Down,
+    Up,
+    NumLockClear,
+    NumPadDivide,
+    NumPadMultiply,
+    NumPadMinus,
+    NumPadPlus,
+    NumPadEnter,
+    NumPad1,
+    NumPad2,
+    NumPad3,
+    NumPad4,
+    NumPad5,
+    NumPad6,
+    NumPad7,
+    NumPad8,
+    NumPad9,
+    NumPad0,
+    NumPadPeriod,
+    Application,
+    Power,
+    NumPadEquals,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+    Execute,
+    Help,
+    Menu,
+    Select,
+    Stop,
+    Again,
+    Undo,
+    Cut,
+    Copy,
+    Paste,
+    Find,
+    Mute,
+    VolumeUp,
+    VolumeDown,
+    NumPadComma,
+    NumPadEqualsAS400,
+    AltErase,
+    Sysreq,
+    Cancel,
+    Clear,
+    Prior,
+    Return2,
+    Separator,
+    Out,
+    Oper,
+    ClearAgain,
+    CrSel,
+    ExSel,
+    NumPad00,
+    NumPad000,
+    ThousandsSeparator,
+    DecimalSeparator,
+    CurrencyUnit,
+    CurrencySubUnit,
+    NumPadLeftParen,
+    NumPadRightParen,
+    NumPadLeftBrace,
+    NumPadRightBrace,
+    NumPadTab,
+    NumPadBackspace,
+    NumPadA,
+    NumPadB,
+    NumPadC,
+    NumPadD,
+    NumPadE,
+    NumPadF,
+    NumPadXor,
+    NumPadPower,
+    NumPadPercent,
+    NumPadLess,
+    NumPadGreater,
+    NumPadAmpersand,
+    NumPadDblAmpersand,
+    NumPadVerticalBar,
+    NumPadDblVerticalBar,
+    NumPadColon,
+    NumPadHash,
+    NumPadSpace,
+    NumPadAt,
+    NumPadExclam,
+    NumPadMemStore,
+    NumPadMemRecall,
+    NumPadMemClear,
+    NumPadMemAdd,
+    NumPadMemSubtract,
+    NumPadMemMultiply,
+    NumPadMemDivide,
+    NumPadPlusMinus,
+    NumPadClear,
+    NumPadClearEntry,
+    NumPadBinary,
+    NumPadOctal,
+    NumPadDecimal,
+    NumPadHexadecimal,
+    LCtrl,
+    LShift,
+    LAlt,
+    LGui,
+    RCtrl,
+    RShift,
+    RAlt,
+    RGui,
+    Mode,
+    AudioNext,
+    AudioPrev,
+    AudioStop,
+    AudioPlay,
+    AudioMute,
+    MediaSelect,
+    Www,
+    Mail,
+    Calculator,
+    Computer,
+    AcSearch,
+    AcHome,
+    AcBack,
+    AcForward,
+    AcStop,
+    AcRefresh,
+    AcBookmarks,
+    BrightnessDown,
+    BrightnessUp,
+    DisplaySwitch,
+    KbdIllumToggle,
+    KbdIllumDown,
+    KbdIllumUp,
+    Eject,
+    Sleep,
+}
+
+/// Represent the physical position of a keyboard key.
+///
+/// Unlike `Key`, a scancode does not depend on the keyboard layout.
+/// For example, `Scancode::W` is the key above `Scancode::S`
+/// on both QWERTY and AZERTY keyboards, while it produces `Key::Z` on AZERTY.
+/// Use scancodes to bind keys by position, and `Key` to display them.
+/// Scancodes follows SDL https://wiki.libsdl.org/SDLScancodeLookup
+#[allow(missing_docs)]
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq, Eq,
+    PartialOrd, Ord, Debug, Hash)]
+pub enum Scancode {
+    Unknown                 = 0,
+    A                       = 4,
+    B                       = 5,
+    C                       = 6,
+    D                       = 7,
+    E                       = 8,
+    F                       = 9,
+    G                       = 10,
+    H                       = 11,
+    I                       = 12,
+    J                       = 13,
+    K                       = 14,
+    L                       = 15,
+    M                       = 16,
+    N                       = 17,
+    O                       = 18,
+    P                       = 19,
+    Q                       = 20,
+    R                       = 21,
+    S                       = 22,
+    T                       = 23,
+    U                       = 24,
+    V                       = 25,
+    W                       = 26,
+    X                       = 27,
+    Y                       = 28,
+    Z                       = 29,
+    D1                      = 30,
+    D2                      = 31,
+    D3                      = 32,
+    D4                      = 33,
+    D5                      = 34,
+    D6                      = 35,
+    D7                      = 36,
+    D8                      = 37,
+    D9                      = 38,
+    D0                      = 39,
+    Return                  = 40,
+    Escape                  = 41,
+    Backspace               = 42,
+    Tab                     = 43,
+    Space                   = 44,
+    Minus                   = 45,
+    Equals                  = 46,
+    LeftBracket             = 47,
+    RightBracket            = 48,
+    Backslash               = 49,
+    NonUsHash               = 50,
+    Semicolon               = 51,
+    Apostrophe              = 52,
+    Grave                   = 53,
+    Comma                   = 54,
+    Period                  = 55,
+    Slash                   = 56,
+    CapsLock                = 57,
+    F1                      = 58,
+    F2                      = 59,
+    F3                      = 60,
+    F4                      = 61,
+    F5                      = 62,
+    F6                      = 63,
+    F7                      = 64,
+    F8                      = 65,
+    F9                      = 66,
+    F10                     = 67,
+    F11                     = 68,
+    F12                     = 69,
+    PrintScreen             = 70,
+    ScrollLock              = 71,
+    Pause                   = 72,
+    Insert                  = 73,
+    Home                    = 74,
+    PageUp                  = 75,
+    Delete                  = 76,
+    End                     = 77,
+    PageDown                = 78,
+    Right                   = 79,
+    Left                    = 80,
+    Down                    = 81,
+    Up                      = 82,
+    NumLockClear            = 83,
+    NumPadDivide            = 84,
+    NumPadMultiply          = 85,
+    NumPadMinus             = 86,
+    NumPadPlus              = 87,
+    NumPadEnter             = 88,
+    NumPad1                 = 89,
+    NumPad2                 = 90,
+    NumPad3                 = 91,
+    NumPad4                 = 92,
+    NumPad5                 = 93,
+    NumPad6                 = 94,
+    NumPad7                 = 95,
+    NumPad8                 = 96,
+    NumPad9                 = 97,
+    NumPad0                 = 98,
+    NumPadPeriod            = 99,
+    NonUsBackslash          = 100,
+    Application             = 101,
+    Power                   = 102,
+    NumPadEquals            = 103,
+    F13                     = 104,
+    F14                     = 105,
+    F15                     = 106,
+    F16                     = 107,
+    F17                     = 108,
+    F18                     = 109,
+    F19                     = 110,
+    F20                     = 111,
+    F21                     = 112,
+    F22                     = 113,
+    F23                     = 114,
+    F24                     = 115,
+    Execute                 = 116,
+    Help                    = 117,
+    Menu                    = 118,
+    Select                  = 119,
+    Stop                    = 120,
+    Again                   = 121,
+    Undo                    = 122,
+    Cut                     = 123,
+    Copy                    = 124,
+    Paste                   = 125,
+    Find                    = 126,
+    Mute                    = 127,
+    VolumeUp                = 128,
+    VolumeDown              = 129,
+    NumPadComma             = 133,
+    NumPadEqualsAS400       = 134,
+    International1          = 135,
+    International2          = 136,
+    International3          = 137,
+    International4          = 138,
+    International5          = 139,
+    International6          = 140,
+    International7          = 141,
+    International8          = 142,
+    International9          = 143,
+    Lang1                   = 144,
+    Lang2                   = 145,
+    Lang3                   = 146,
+    Lang4                   = 147,
+    Lang5                   = 148,
+    Lang6                   = 149,
+    Lang7                   = 150,
+    Lang8                   = 151,
+    Lang9                   = 152,
+    AltErase                = 153,
+    Sysreq                  = 154,
+    Cancel                  = 155,
+    Clear                   = 156,
+    Prior                   = 157,
+    Return2                 = 158,
+    Separator               = 159,
+    Out                     = 160,
+    Oper                    = 161,
+    ClearAgain              = 162,
+    CrSel                   = 163,
+    ExSel                   = 164,
+    LCtrl                   = 224,
+    LShift                  = 225,
+    LAlt                    = 226,
+    LGui                    = 227,
+    RCtrl                   = 228,
+    RShift                  = 229,
+    RAlt                    = 230,
+    RGui                    = 231,
+    Mode                    = 257,
+    AudioNext               = 258,
+    AudioPrev               = 259,
+    AudioStop               = 260,
+    AudioPlay               = 261,
+    AudioMute               = 262,
+    MediaSelect             = 263,
+    Www                     = 264,
+    Mail                    = 265,
+    Calculator              = 266,
+    Computer                = 267,
+    AcSearch                = 268,
+    AcHome                  = 269,
+    AcBack                  = 270,
+    AcForward               = 271,
+    AcStop                  = 272,
+    AcRefresh               = 273,
+    AcBookmarks             = 274,
+    BrightnessDown          = 275,
+    BrightnessUp            = 276,
+    DisplaySwitch           = 277,
+    KbdIllumToggle          = 278,
+    KbdIllumDown            = 279,
+    KbdIllumUp              = 280,
+    Eject                   = 281,
+    Sleep                   = 282,
+}
+
+impl From<u32> for Scancode {
+    fn from(val: u32) -> Scancode {
+        match val {
+            4 => Scancode::A,
+            5 => Scancode::B,
+            6 => Scancode::C,
+            7 => Scancode::D,
+            8 => Scancode::E,
+            9 => Scancode::F,
+            10 => Scancode::G,
+            11 => Scancode::H,
+            12 => Scancode::I,
+            13 => Scancode::J,
+            14 => Scancode::K,
+            15 => Scancode::L,
+            16 => Scancode::M,
+            17 => Scancode::N,
+            18 => Scancode::O,
+            19 => Scancode::P,
+            20 => Scancode::Q,
+            21 => Scancode::R,
+            22 => Scancode::S,
+            23 => Scancode::T,
+            24 => Scancode::U,
+            25 => Scancode::V,
+            26 => Scancode::W,
+            27 => Scancode::X,
+            28 => Scancode::Y,
+            29 => Scancode::Z,
+            30 => Scancode::D1,
+            31 => Scancode::D2,
+            32 => Scancode::D3,
+            33 => Scancode::D4,
+            34 => Scancode::D5,
+            35 => Scancode::D6,
+            36 => Scancode::D7,
+            37 => Scancode::D8,
+            38 => Scancode::D9,
+            39 => Scancode::D0,
+            40 => Scancode::Return,
+            41 => Scancode::Escape,
+            42 => Scancode::Backspace,
+            43 => Scancode::Tab,
+            44 => Scancode::Space,
+            45 => Scancode::Minus,
+            46 => Scancode::Equals,
+            47 => Scancode::LeftBracket,
+            48 => Scancode::RightBracket,
+            49 => Scancode::Backslash,
+            50 => Scancode::NonUsHash,
+            51 => Scancode::Semicolon,
+            52 => Scancode::Apostrophe,
+            53 => Scancode::Grave,
+            54 => Scancode::Comma,
+            55 => Scancode::Period,
+            56 => Scancode::Slash,
+            57 => Scancode::CapsLock,
+            58 => Scancode::F1,
+            59 => Scancode::F2,
+            60 => Scancode::F3,
+            61 => Scancode::F4,
+            62 => Scancode::F5,
+            63 => Scancode::F6,
+            64 => Scancode::F7,
+            65 => Scancode::F8,
+            66 => Scancode::F9,
+            67 => Scancode::F10,
+            68 => Scancode::F11,
+            69 => Scancode::F12,
+            70 => Scancode::PrintScreen,
+            71 => Scancode::ScrollLock,
+            72 => Scancode::Pause,
+            73 => Scancode::Insert,
+            74 => Scancode::Home,
+            75 => Scancode::PageUp,
+            76 => Scancode::Delete,
+            77 => Scancode::End,
+            78 => Scancode::PageDown,
+            79 => Scancode::Right,
+            80 => Scancode::Left,
+            81 => Scancode::Down,
+            82 => Scancode::Up,
+            83 => Scancode::NumLockClear,
+            84 => Scancode::NumPadDivide,
+            85 => Scancode::NumPadMultiply,
+            86 => Scancode::NumPadMinus,
+            87 => Scancode::NumPadPlus,
+            88 => Scancode::NumPadEnter,
+            89 => Scancode::NumPad1,
+            90 => Scancode::NumPad2,
+            91 => Scancode::NumPad3,
+            92 => Scancode::NumPad4,
+            93 => Scancode::NumPad5,
+            94 => Scancode::NumPad6,
+            95 => Scancode::NumPad7,
+            96 => Scancode::NumPad8,
+            97 => Scancode::NumPad9,
+            98 => Scancode::NumPad0,
+            99 => Scancode::NumPadPeriod,
+            100 => Scancode::NonUsBackslash,
+            101 => Scancode::Application,
+            102 => Scancode::Power,
+            103 => Scancode::NumPadEquals,
+            104 => Scancode::F13,
+            105 => Scancode::F14,
+            106 => Scancode::F15,
+            107 => Scancode::F16,
+            108 => Scancode::F17,
+            109 => Scancode::F18,
+            110 => Scancode::F19,
+            111 => Scancode::F20,
+            112 => Scancode::F21,
+            113 => Scancode::F22,
+            114 => Scancode::F23,
+            115 => Scancode::F24,
+            116 => Scancode::Execute,
+            117 => Scancode::Help,
+            118 => Scancode::Menu,
+            119 => Scancode::Select,
+            120 => Scancode::Stop,
+            121 => Scancode::Again,
+            122 => Scancode::Undo,
+            123 => Scancode::Cut,
+            124 => Scancode::Copy,
+            125 => Scancode::Paste,
+            126 => Scancode::Find,
+            127 => Scancode::Mute,
+            128 => Scancode::VolumeUp,
+            129 => Scancode::VolumeDown,
+            133 => Scancode::NumPadComma,
+            134 => Scancode::NumPadEqualsAS400,
+            135 => Scancode::International1,
+            136 => Scancode::International2,
+            137 => Scancode::International3,
+            138 => Scancode::International4,
+            139 => Scancode::International5,
+            140 => Scancode::International6,
+            141 => Scancode::International7,
+            142 => Scancode::International8,
+            143 => Scancode::International9,
+            144 => Scancode::Lang1,
+            145 => Scancode::Lang2,
+            146 => Scancode::Lang3,
+            147 => Scancode::Lang4,
+            148 => Scancode::Lang5,
+            149 => Scancode::Lang6,
+            150 => Scancode::Lang7,
+            151 => Scancode::Lang8,
+            152 => Scancode::Lang9,
+            153 => Scancode::AltErase,
+            154 => Scancode::Sysreq,
+            155 => Scancode::Cancel,
+            156 => Scancode::Clear,
+            157 => Scancode::Prior,
+            158 => Scancode::Return2,
+            159 => Scancode::Separator,
+            160 => Scancode::Out,
+            161 => Scancode::Oper,
+            162 => Scancode::ClearAgain,
+            163 => Scancode::CrSel,
+            164 => Scancode::ExSel,
+            224 => Scancode::LCtrl,
+            225 => Scancode::LShift,
+            226 => Scancode::LAlt,
+            227 => Scancode::LGui,
+            228 => Scancode::RCtrl,
+            229 => Scancode::RShift,
+            230 => Scancode::RAlt,
+            231 => Scancode::RGui,
+            257 => Scancode::Mode,
+            258 => Scancode::AudioNext,
+            259 => Scancode::AudioPrev,
+            260 => Scancode::AudioStop,
+            261 => Scancode::AudioPlay,
+            262 => Scancode::AudioMute,
+            263 => Scancode::MediaSelect,
+            264 => Scancode::Www,
+            265 => Scancode::Mail,
+            266 => Scancode::Calculator,
+            267 => Scancode::Computer,
+            268 => Scancode::AcSearch,
+            269 => Scancode::AcHome,
+            270 => Scancode::AcBack,
+            271 => Scancode::AcForward,
+            272 => Scancode::AcStop,
+            273 => Scancode::AcRefresh,
+            274 => Scancode::AcBookmarks,
+            275 => Scancode::BrightnessDown,
+            276 => Scancode::BrightnessUp,
+            277 => Scancode::DisplaySwitch,
+            278 => Scancode::KbdIllumToggle,
+            279 => Scancode::KbdIllumDown,
+            280 => Scancode::KbdIllumUp,
+            281 => Scancode::Eject,
+            282 => Scancode::Sleep,
+            _ => Scancode::Unknown,
+        }
+    }
+}
+
+impl From<Scancode> for u32 {
+    #[inline(always)]
+    fn from(scancode: Scancode) -> u32 {
+        scancode as u32
+    }
+}
+
+names! { Scancode, "Unknown scancode `{}`",
+    Unknown,
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    D1,
+    D2,
+    D3,
+    D4,
+    D5,
+    D6,
+    D7,
+    D8,
+    D9,
+    D0,
+    Return,
+    Escape,
+    Backspace,
+    Tab,
+    Space,
+    Minus,
+    Equals,
+    LeftBracket,
+    RightBracket,
+    Backslash,
+    NonUsHash,
+    Semicolon,
+    Apostrophe,
+    Grave,
+    Comma,
+    Period,
+    Slash,
+    CapsLock,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    PrintScreen,
+    ScrollLock,
+    Pause,
+    Insert,
+    Home,
+    PageUp,
+    Delete,
+    End,
+    PageDown,
+    Right,
+    Left,
+    Down,
+    Up,
+    NumLockClear,
+    NumPadDivide,
+    NumPadMultiply,
+    NumPadMinus,
+    NumPadPlus,
+    NumPadEnter,
+    NumPad1,
+    NumPad2,
+    NumPad3,
+    NumPad4,
+    NumPad5,
+    NumPad6,
+    NumPad7,
+    NumPad8,
+    NumPad9,
+    NumPad0,
+    NumPadPeriod,
+    NonUsBackslash,
+    Application,
+    Power,
+    NumPadEquals,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+    Execute,
+    Help,
+    Menu,
+    Select,
+    Stop,
+    Again,
+    Undo,
+    Cut,
+    Copy,
+    Paste,
+    Find,
+    Mute,
+    VolumeUp,
+    VolumeDown,
+    NumPadComma,
+    NumPadEqualsAS400,
+    International1,
+    International2,
+    International3,
+    International4,
+    International5,
+    International6,
+    International7,
+    International8,
+    International9,
+    Lang1,
+    Lang2,
+    Lang3,
+    Lang4,
+    Lang5,
+    Lang6,
+    Lang7,
+    Lang8,
+    Lang9,
+    AltErase,
+    Sysreq,
+    Cancel,
+    Clear,
+    Prior,
+    Return2,
+    Separator,
+    Out,
+    Oper,
+    ClearAgain,
+    CrSel,
+    ExSel,
+    LCtrl,
+    LShift,
+    LAlt,
+    LGui,
+    RCtrl,
+    RShift,
+    RAlt,
+    RGui,
+    Mode,
+    AudioNext,
+    AudioPrev,
+    AudioStop,
+    AudioPlay,
+    AudioMute,
+    MediaSelect,
+    Www,
+    Mail,
+    Calculator,
+    Computer,
+    AcSearch,
+    AcHome,
+    AcBack,
+    AcForward,
+    AcStop,
+    AcRefresh,
+    AcBookmarks,
+    BrightnessDown,
+    BrightnessUp,
+    DisplaySwitch,
+    KbdIllumToggle,
+    KbdIllumDown,
+    KbdIllumUp,
+    Eject,
+    Sleep,
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -878,4 +1791,243 @@ mod tests {
             assert_eq!(key, key2);
         }
     }
+
+    #[test]
+    fn scancode() {
+        use super::Scancode;
+        use super::Scancode::*;
+
+        let scancodes = vec![
+            Unknown,
+            A,
+            B,
+            C,
+            D,
+            E,
+            F,
+            G,
+            H,
+            I,
+            J,
+            K,
+            L,
+            M,
+            N,
+            O,
+            P,
+            Q,
+            R,
+            S,
+            T,
+            U,
+            V,
+            W,
+            X,
+            Y,
+            Z,
+            D1,
+            D2,
+            D3,
+            D4,
+            D5,
+            D6,
+            D7,
+            D8,
+            D9,
+            D0,
+            Return,
+            Escape,
+            Backspace,
+            Tab,
+            Space,
+            Minus,
+            Equals,
+            LeftBracket,
+            RightBracket,
+            Backslash,
+            NonUsHash,
+            Semicolon,
+            Apostrophe,
+            Grave,
+            Comma,
+            Period,
+            Slash,
+            CapsLock,
+            F1,
+            F2,
+            F3,
+            F4,
+            F5,
+            F6,
+            F7,
+            F8,
+            F9,
+            F10,
+            F11,
+            F12,
+            PrintScreen,
+            ScrollLock,
+            Pause,
+            Insert,
+            Home,
+            PageUp,
+            Delete,
+            End,
+            PageDown,
+            Right,
+            Left,
+            Down,
+            Up,
+            NumLockClear,
+            NumPadDivide,
+            NumPadMultiply,
+            NumPadMinus,
+            NumPadPlus,
+            NumPadEnter,
+            NumPad1,
+            NumPad2,
+            NumPad3,
+            NumPad4,
+            NumPad5,
+            NumPad6,
+            NumPad7,
+            NumPad8,
+            NumPad9,
+            NumPad0,
+            NumPadPeriod,
+            NonUsBackslash,
+            Application,
+            Power,
+            NumPadEquals,
+            F13,
+            F14,
+            F15,
+            F16,
+            F17,
+            F18,
+            F19,
+            F20,
+            F21,
+            F22,
+            F23,
+            F24,
+            Execute,
+            Help,
+            Menu,
+            Select,
+            Stop,
+            Again,
+            Undo,
+            Cut,
+            Copy,
+            Paste,
+            Find,
+            Mute,
+            VolumeUp,
+            VolumeDown,
+            NumPadComma,
+            NumPadEqualsAS400,
+            International1,
+            International2,
+            International3,
+            International4,
+            International5,
+            International6,
+            International7,
+            International8,
+            International9,
+            Lang1,
+            Lang2,
+            Lang3,
+            Lang4,
+            Lang5,
+            Lang6,
+            Lang7,
+            Lang8,
+            Lang9,
+            AltErase,
+            Sysreq,
+            Cancel,
+            Clear,
+            Prior,
+            Return2,
+            Separator,
+            Out,
+            Oper,
+            ClearAgain,
+            CrSel,
+            ExSel,
+            LCtrl,
+            LShift,
+            LAlt,
+            LGui,
+            RCtrl,
+            RShift,
+            RAlt,
+            RGui,
+            Mode,
+            AudioNext,
+            AudioPrev,
+            AudioStop,
+            AudioPlay,
+            AudioMute,
+            MediaSelect,
+            Www,
+            Mail,
+            Calculator,
+            Computer,
+            AcSearch,
+            AcHome,
+            AcBack,
+            AcForward,
+            AcStop,
+            AcRefresh,
+            AcBookmarks,
+            BrightnessDown,
+            BrightnessUp,
+            DisplaySwitch,
+            KbdIllumToggle,
+            KbdIllumDown,
+            KbdIllumUp,
+            Eject,
+            Sleep,
+        ];
+        for &scancode in &scancodes {
+            let val: u32 = scancode.into();
+            let scancode2: Scancode = val.into();
+            assert_eq!(scancode, scancode2);
+        }
+    }
+
+    #[test]
+    fn key_from_str() {
+        use super::Key;
+
+        for val in (0..0x80).chain(0x40000039..0x40000120) {
+            let key: Key = val.into();
+            assert_eq!(key.to_string().parse(), Ok(key));
+        }
+        assert_eq!(Key::NumPadEnter.to_string(), "NumPadEnter");
+        assert!("Enter".parse::<Key>().is_err());
+    }
+
+    #[test]
+    fn scancode_from_str() {
+        use super::Scancode;
+
+        for val in 0..0x120 {
+            let scancode: Scancode = val.into();
+            assert_eq!(scancode.to_string().parse(), Ok(scancode));
+        }
+    }
+
+    #[test]
+    fn modifier_key_from_str() {
+        use super::{ ModifierKey, CTRL_SHIFT, NO_MODIFIER };
+
+        assert_eq!(CTRL_SHIFT.to_string(), "Ctrl+Shift");
+        assert_eq!("Ctrl+Shift".parse(), Ok(CTRL_SHIFT));
+        assert_eq!("".parse(), Ok(NO_MODIFIER));
+        assert!("Ctrl+Meta".parse::<ModifierKey>().is_err());
+    }
 }
@@ -9,65 +9,131 @@
 extern crate bitflags;
 extern crate rustc_serialize;
 extern crate viewport;
+extern crate unicode_segmentation;
 
-pub use mouse::MouseButton;
-pub use keyboard::Key;
-pub use controller::{ ControllerAxisArgs, ControllerButton };
+use std::fmt;
+use std::str::FromStr;
+
+pub use mouse::{ MouseButton, MouseCursorArgs, MouseRelativeArgs, ScrollArgs, ScrollPhase,
+                 ScrollUnit };
+pub use keyboard::{ Key, Scancode };
+pub use controller::{ ControllerAxisArgs, ControllerButton, ControllerConnection };
+pub use gamepad::{ GamepadAxis, GamepadButton, GamepadDb, GamepadMapping };
 
 pub mod controller;
+pub mod gamepad;
 pub mod keyboard;
 pub mod mouse;
 
+pub use action::{ ActionEvent, ActionMap, Binding, Trigger };
 pub use after_render::{ AfterRenderArgs, AfterRenderEvent };
-pub use controller::{ ControllerAxisEvent };
+pub use axis::{ AxisFilter, AxisSettings, ResponseCurve };
+pub use click::{ Click, ClickSettings, ClickTracker };
+pub use composition::{ Composition, CompositionEvent };
+pub use controller::{ ControllerAxisEvent, ControllerConnectionEvent };
 pub use cursor::CursorEvent;
+pub use custom::{ CustomArgs, CustomEvent };
+pub use dispatch::{ DispatchPhase, Dispatcher, NodeId };
+pub use drag::{ Drag, DragTracker };
 pub use event::Event;
+pub use file_drag::{ FileDrag, FileDragEvent };
 pub use focus::FocusEvent;
-pub use generic_event::GenericEvent;
+pub use generic_event::{ ArgsError, GenericEvent };
+pub use gesture::{ Gesture, GestureRecognizer, GestureSettings, SwipeDirection };
 pub use idle::{ IdleArgs, IdleEvent };
+pub use logical::{ LogicalViewport, ScaleMode };
 pub use mouse::{ MouseCursorEvent, MouseRelativeEvent, MouseScrollEvent };
+pub use pen::{ PenArgs, PenEvent, PenTool };
 pub use press::PressEvent;
 pub use release::ReleaseEvent;
+pub use repeat::KeyRepeat;
 pub use resize::ResizeEvent;
+pub use state::InputState;
 pub use render::{ RenderArgs, RenderEvent };
 pub use text::TextEvent;
+pub use text_input::TextInput;
+pub use timestamped::Timestamped;
 pub use touch::{ Touch, TouchArgs, TouchEvent };
 pub use update::{ UpdateArgs, UpdateEvent };
 
 pub mod generic_event;
 
+mod action;
 mod after_render;
+mod axis;
+mod click;
+mod composition;
 mod cursor;
+mod custom;
+mod dispatch;
+mod drag;
 mod event;
+mod file_drag;
 mod focus;
+mod gesture;
 mod idle;
+mod logical;
+mod pen;
 mod press;
 mod release;
+mod repeat;
 mod render;
 mod resize;
+mod state;
 mod text;
+mod text_input;
+mod timestamped;
 mod touch;
 mod update;
 
+/// The device id of keyboards and mice, used by backends
+/// that can not tell devices apart.
+pub const DEFAULT_DEVICE: i64 = 0;
+
 /// Used to identify events arguments provided by traits.
+///
+/// The ids of built-in events are constants in this crate, for example `RENDER`.
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub struct EventId(pub &'static str);
 
-const AFTER_RENDER: EventId = EventId("piston/after_render");
-const CONTROLLER_AXIS: EventId = EventId("piston/controller_axis");
-const CURSOR: EventId = EventId("piston/cursor");
-const FOCUS: EventId = EventId("piston/focus");
-const IDLE: EventId = EventId("piston/idle");
-const MOUSE_SCROLL: EventId = EventId("piston/mouse_scroll");
-const MOUSE_RELATIVE: EventId = EventId("piston/mouse_relative");
-const MOUSE_CURSOR: EventId = EventId("piston/mouse_cursor");
-const PRESS: EventId = EventId("piston/press");
-const RELEASE: EventId = EventId("piston/release");
-const RENDER: EventId = EventId("piston/render");
-const RESIZE: EventId = EventId("piston/resize");
-const TEXT: EventId = EventId("piston/text");
-const TOUCH: EventId = EventId("piston/touch");
-const UPDATE: EventId = EventId("piston/update");
+/// The id of after render events.
+pub const AFTER_RENDER: EventId = EventId("piston/after_render");
+/// The id of IME composition events.
+pub const COMPOSITION: EventId = EventId("piston/composition");
+/// The id of controller axis events.
+pub const CONTROLLER_AXIS: EventId = EventId("piston/controller_axis");
+/// The id of controller connection events.
+pub const CONTROLLER_CONNECTION: EventId = EventId("piston/controller_connection");
+/// The id of cursor enter and leave events.
+pub const CURSOR: EventId = EventId("piston/cursor");
+/// The id of file drag events.
+pub const FILE_DRAG: EventId = EventId("piston/file_drag");
+/// The id of focus events.
+pub const FOCUS: EventId = EventId("piston/focus");
+/// The id of idle events.
+pub const IDLE: EventId = EventId("piston/idle");
+/// The id of mouse scroll events.
+pub const MOUSE_SCROLL: EventId = EventId("piston/mouse_scroll");
+/// The id of relative mouse motion events.
+pub const MOUSE_RELATIVE: EventId = EventId("piston/mouse_relative");
+/// The id of mouse cursor events.
+pub const MOUSE_CURSOR: EventId = EventId("piston/mouse_cursor");
+/// The id of pen events.
+pub const PEN: EventId = EventId("piston/pen");
+/// The id of press events.
+pub const PRESS: EventId = EventId("piston/press");
+/// The id of release events.
+pub const RELEASE: EventId = EventId("piston/release");
+/// The id of render events.
+pub const RENDER: EventId = EventId("piston/render");
+/// The id of resize events.
+pub const RESIZE: EventId = EventId("piston/resize");
+/// The id of text events.
+pub const TEXT: EventId = EventId("piston/text");
+/// The id of touch events.
+pub const TOUCH: EventId = EventId("piston/touch");
+/// The id of update events.
+pub const UPDATE: EventId = EventId("piston/update");
 
 /// Models different kinds of buttons.
 #[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq, Eq, Hash, Debug)]
@@ -80,28 +146,81 @@ pub enum Button {
     Controller(ControllerButton),
 }
 
+/// Button arguments of press and release events.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq, Eq, Hash, Debug)]
+pub struct ButtonArgs {
+    /// The button that was pressed or released.
+    pub button: Button,
+    /// The physical position of the key, for keyboard buttons.
+    ///
+    /// This is `None` for other buttons, or when the backend does not report it.
+    pub scancode: Option<Scancode>,
+    /// Whether the press is generated by holding down a key,
+    /// instead of a fresh press.
+    ///
+    /// This is always `false` for release events.
+    pub repeat: bool,
+    /// A unique identifier for the keyboard or mouse.
+    ///
+    /// This is `DEFAULT_DEVICE` when the backend can not tell devices apart,
+    /// and for controller buttons, which have their own id.
+    pub device: i64,
+}
+
+impl ButtonArgs {
+    /// Creates button arguments without a scancode.
+    pub fn new(button: Button) -> ButtonArgs {
+        ButtonArgs {
+            button: button,
+            scancode: None,
+            repeat: false,
+            device: DEFAULT_DEVICE,
+        }
+    }
+
+    /// Sets the scancode.
+    pub fn scancode(mut self, scancode: Scancode) -> ButtonArgs {
+        self.scancode = Some(scancode);
+        self
+    }
+
+    /// Sets whether the press is a repeat.
+    pub fn repeat(mut self, repeat: bool) -> ButtonArgs {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Sets the device id.
+    pub fn device(mut self, device: i64) -> ButtonArgs {
+        self.device = device;
+        self
+    }
+}
+
 /// Models different kinds of motion.
 #[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq, Debug)]
 pub enum Motion {
-    /// x and y in window coordinates.
-    MouseCursor(f64, f64),
+    /// x and y in window coordinates, with the device id.
+    MouseCursor(MouseCursorArgs),
     /// x and y in relative coordinates.
-    MouseRelative(f64, f64),
-    /// x and y in scroll ticks.
-    MouseScroll(f64, f64),
+    MouseRelative(MouseRelativeArgs),
+    /// Scroll delta with unit and phase.
+    MouseScroll(ScrollArgs),
     /// controller axis move event.
     ControllerAxis(ControllerAxisArgs),
     /// touch event.
     Touch(TouchArgs),
+    /// pen event, for example from a graphics tablet.
+    Pen(PenArgs),
 }
 
 /// Models input events.
 #[derive(Clone, RustcDecodable, RustcEncodable, PartialEq, Debug)]
 pub enum Input {
     /// Pressed a button.
-    Press(Button),
+    Press(ButtonArgs),
     /// Released a button.
-    Release(Button),
+    Release(ButtonArgs),
     /// Moved mouse cursor.
     Move(Motion),
     /// Text (usually from keyboard).
@@ -112,6 +231,39 @@ pub enum Input {
     Focus(bool),
     /// Window gained or lost cursor.
     Cursor(bool),
+    /// A controller was connected or disconnected.
+    ControllerConnection(ControllerConnection),
+    /// Text composition with an input method editor changed.
+    Composition(Composition),
+    /// Files were dragged onto the window.
+    FileDrag(FileDrag),
+}
+
+/// Formats keys by name, for example `F5`,
+/// mouse buttons as `Mouse Left`
+/// and controller buttons as `Pad0 Button3`.
+impl fmt::Display for Button {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Button::Keyboard(key) => fmt::Display::fmt(&key, f),
+            Button::Mouse(button) => fmt::Display::fmt(&button, f),
+            Button::Controller(button) => fmt::Display::fmt(&button, f),
+        }
+    }
+}
+
+impl FromStr for Button {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Button, String> {
+        if s.starts_with("Mouse ") {
+            s.parse().map(Button::Mouse)
+        } else if s.starts_with("Pad") {
+            s.parse().map(Button::Controller)
+        } else {
+            s.parse().map(Button::Keyboard)
+        }
+    }
 }
 
 impl From<Key> for Button {
@@ -132,6 +284,12 @@ impl From<ControllerButton> for Button {
     }
 }
 
+impl From<Button> for ButtonArgs {
+    fn from(button: Button) -> Self {
+        ButtonArgs::new(button)
+    }
+}
+
 impl From<ControllerAxisArgs> for Motion {
     fn from(args: ControllerAxisArgs) -> Self {
         Motion::ControllerAxis(args)
@@ -0,0 +1,247 @@
+//! Maps pointer positions to logical coordinates of a scaled viewport.
+//!
+//! A game often draws a logical area of fixed size,
+//! for example 320x240, scaled to fit the window.
+//! [`LogicalViewport`](./struct.LogicalViewport.html) converts between
+//! window coordinates of pointer events and the logical coordinates.
+
+use viewport::Viewport;
+
+use { GenericEvent, TouchArgs };
+
+/// How the logical area is scaled to the viewport.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ScaleMode {
+    /// Fills the viewport, changing the aspect ratio.
+    Stretch,
+    /// Fits inside the viewport, keeping the aspect ratio.
+    ///
+    /// The remaining parts of the viewport are left as bars (letterboxing).
+    Fit,
+    /// Fills the viewport, keeping the aspect ratio.
+    ///
+    /// Parts of the logical area outside the viewport are cut off.
+    Fill,
+    /// Fits inside the viewport with a whole number of pixels per logical unit.
+    ///
+    /// The scale is at least 1, so the logical area might not fit in a small viewport.
+    Integer,
+}
+
+/// A logical area scaled to a viewport.
+///
+/// - Window coordinates are in points with the origin in the upper left corner,
+///   like the mouse cursor
+/// - Logical coordinates have the origin in the upper left corner of the logical area
+/// - The logical area is centered in the viewport
+///
+/// Conversions return `None` while the window, the viewport
+/// or the logical area has zero size, for example when the window is minimized.
+#[derive(Copy, Clone)]
+pub struct LogicalViewport {
+    /// The viewport, for example from `RenderArgs::viewport`.
+    pub viewport: Viewport,
+    /// The size of the logical area.
+    pub size: [f64; 2],
+    /// How the logical area is scaled.
+    pub mode: ScaleMode,
+}
+
+impl LogicalViewport {
+    /// Creates a new logical viewport.
+    ///
+    /// The size must be non-zero.
+    pub fn new(viewport: Viewport, size: [f64; 2], mode: ScaleMode) -> LogicalViewport {
+        LogicalViewport {
+            viewport: viewport,
+            size: size,
+            mode: mode,
+        }
+    }
+
+    /// Returns the number of pixels per logical unit in x and y direction.
+    ///
+    /// Returns zero if the logical area has zero size.
+    pub fn scale(&self) -> [f64; 2] {
+        if self.size[0] <= 0.0 || self.size[1] <= 0.0 { return [0.0; 2]; }
+        let rect = self.viewport_rect();
+        let sx = rect[2] / self.size[0];
+        let sy = rect[3] / self.size[1];
+        match self.mode {
+            ScaleMode::Stretch => [sx, sy],
+            ScaleMode::Fit => [sx.min(sy); 2],
+            ScaleMode::Fill => [sx.max(sy); 2],
+            ScaleMode::Integer => [sx.min(sy).floor().max(1.0); 2],
+        }
+    }
+
+    /// Returns the logical area in pixels, `[x, y, width, height]`,
+    /// where `(x, y)` is the upper left corner.
+    ///
+    /// The width and height are zero if the logical area has zero size.
+    pub fn rect(&self) -> [f64; 4] {
+        let rect = self.viewport_rect();
+        let scale = self.scale();
+        let w = self.size[0] * scale[0];
+        let h = self.size[1] * scale[1];
+        [rect[0] + 0.5 * (rect[2] - w), rect[1] + 0.5 * (rect[3] - h), w, h]
+    }
+
+    /// Converts a position from window coordinates to logical coordinates.
+    pub fn to_logical(&self, pos: [f64; 2]) -> Option<[f64; 2]> {
+        if self.is_empty() { return None; }
+        let rect = self.rect();
+        let pixels = self.pixels_per_point();
+        Some([
+            (pos[0] * pixels[0] - rect[0]) * self.size[0] / rect[2],
+            (pos[1] * pixels[1] - rect[1]) * self.size[1] / rect[3],
+        ])
+    }
+
+    /// Converts a position from logical coordinates to window coordinates.
+    pub fn to_window(&self, pos: [f64; 2]) -> Option<[f64; 2]> {
+        if self.is_empty() { return None; }
+        let rect = self.rect();
+        let pixels = self.pixels_per_point();
+        Some([
+            (rect[0] + pos[0] * rect[2] / self.size[0]) / pixels[0],
+            (rect[1] + pos[1] * rect[3] / self.size[1]) / pixels[1],
+        ])
+    }
+
+    /// Returns true if a position in logical coordinates is inside the logical area.
+    pub fn contains(&self, pos: [f64; 2]) -> bool {
+        pos[0] >= 0.0 && pos[0] < self.size[0] &&
+        pos[1] >= 0.0 && pos[1] < self.size[1]
+    }
+
+    /// Returns the mouse cursor position in logical coordinates,
+    /// if this is a mouse cursor event.
+    pub fn mouse_cursor<E: GenericEvent>(&self, e: &E) -> Option<[f64; 2]> {
+        use MouseCursorEvent;
+
+        match e.try_mouse_cursor_full_args() {
+            Ok(Some(args)) => self.to_logical([args.x, args.y]),
+            _ => None,
+        }
+    }
+
+    /// Converts the position of a touch to logical coordinates.
+    pub fn touch_to_logical(&self, args: &TouchArgs) -> Option<[f64; 2]> {
+        let size = self.viewport.window_size;
+        self.to_logical([args.x * size[0] as f64, args.y * size[1] as f64])
+    }
+
+    /// Converts a position from logical coordinates to normalized touch coordinates.
+    pub fn touch_to_window(&self, pos: [f64; 2]) -> Option<[f64; 2]> {
+        let size = self.viewport.window_size;
+        self.to_window(pos).map(|pos| [pos[0] / size[0] as f64, pos[1] / size[1] as f64])
+    }
+
+    // Returns true if a conversion would divide by zero.
+    fn is_empty(&self) -> bool {
+        let viewport = &self.viewport;
+        viewport.window_size[0] == 0 || viewport.window_size[1] == 0 ||
+        viewport.draw_size[0] == 0 || viewport.draw_size[1] == 0 ||
+        viewport.rect[2] <= 0 || viewport.rect[3] <= 0 ||
+        self.size[0] <= 0.0 || self.size[1] <= 0.0
+    }
+
+    // The viewport rectangle in pixels, with the origin in the upper left corner.
+    fn viewport_rect(&self) -> [f64; 4] {
+        let rect = self.viewport.rect;
+        let h = rect[3] as f64;
+        let y = self.viewport.draw_size[1] as f64 - rect[1] as f64 - h;
+        [rect[0] as f64, y, rect[2] as f64, h]
+    }
+
+    fn pixels_per_point(&self) -> [f64; 2] {
+        let draw_size = self.viewport.draw_size;
+        let window_size = self.viewport.window_size;
+        [
+            draw_size[0] as f64 / window_size[0] as f64,
+            draw_size[1] as f64 / window_size[1] as f64,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_logical_viewport() {
+        use viewport::Viewport;
+
+        // A window with 2 pixels per point.
+        let viewport = Viewport {
+            rect: [0, 0, 400, 200],
+            draw_size: [400, 200],
+            window_size: [200, 100],
+        };
+        let logical = |mode| LogicalViewport::new(viewport, [80.0, 80.0], mode);
+        assert_eq!(logical(ScaleMode::Stretch).to_logical([50.0, 25.0]), Some([20.0, 20.0]));
+        assert_eq!(logical(ScaleMode::Fit).rect(), [100.0, 0.0, 200.0, 200.0]);
+        assert_eq!(logical(ScaleMode::Fit).to_logical([50.0, 25.0]), Some([0.0, 20.0]));
+        assert_eq!(logical(ScaleMode::Fill).rect(), [0.0, -100.0, 400.0, 400.0]);
+        assert_eq!(logical(ScaleMode::Fill).to_logical([50.0, 25.0]), Some([20.0, 30.0]));
+        assert_eq!(logical(ScaleMode::Integer).rect(), [120.0, 20.0, 160.0, 160.0]);
+        assert_eq!(logical(ScaleMode::Integer).to_logical([100.0, 50.0]), Some([40.0, 40.0]));
+        assert!(!logical(ScaleMode::Fit).contains(
+            logical(ScaleMode::Fit).to_logical([10.0, 10.0]).unwrap()));
+
+        let fit = logical(ScaleMode::Fit);
+        assert_eq!(fit.to_window(fit.to_logical([60.0, 30.0]).unwrap()), Some([60.0, 30.0]));
+        assert_eq!(fit.touch_to_window([40.0, 40.0]), Some([0.5, 0.5]));
+    }
+
+    #[test]
+    fn test_logical_pointer_events() {
+        use viewport::Viewport;
+        use { Event, Input, Motion, Touch, TouchArgs };
+
+        let viewport = Viewport {
+            rect: [0, 0, 200, 100],
+            draw_size: [200, 100],
+            window_size: [200, 100],
+        };
+        let logical = LogicalViewport::new(viewport, [100.0, 100.0], ScaleMode::Fit);
+        let e: Event = Event::Input(Input::Move(Motion::MouseCursor((100.0, 50.0).into())));
+        assert_eq!(logical.mouse_cursor(&e), Some([50.0, 50.0]));
+        let touch = TouchArgs::new(0, 0, [0.25, 0.0], 1.0, Touch::Start);
+        assert_eq!(logical.touch_to_logical(&touch), Some([0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_logical_minimized_window() {
+        use viewport::Viewport;
+        use { Touch, TouchArgs };
+
+        let viewport = Viewport {
+            rect: [0, 0, 0, 0],
+            draw_size: [0, 0],
+            window_size: [0, 0],
+        };
+        let logical = LogicalViewport::new(viewport, [100.0, 100.0], ScaleMode::Fit);
+        assert_eq!(logical.to_logical([10.0, 20.0]), None);
+        assert_eq!(logical.to_window([10.0, 20.0]), None);
+        let touch = TouchArgs::new(0, 0, [0.25, 0.5], 1.0, Touch::Start);
+        assert_eq!(logical.touch_to_logical(&touch), None);
+        assert_eq!(logical.touch_to_window([0.25, 0.5]), None);
+    }
+
+    #[test]
+    fn test_logical_empty_area() {
+        use viewport::Viewport;
+
+        let viewport = Viewport {
+            rect: [0, 0, 200, 100],
+            draw_size: [200, 100],
+            window_size: [200, 100],
+        };
+        let logical = LogicalViewport::new(viewport, [0.0, 100.0], ScaleMode::Integer);
+        assert_eq!(logical.scale(), [0.0, 0.0]);
+        assert_eq!(logical.rect(), [100.0, 50.0, 0.0, 0.0]);
+        assert_eq!(logical.to_logical([10.0, 20.0]), None);
+    }
+}
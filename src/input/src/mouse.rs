@@ -2,8 +2,162 @@
 //! Back-end agnostic mouse buttons.
 
 use std::any::Any;
+use std::fmt;
+use std::str::FromStr;
 
-use { GenericEvent, MOUSE_SCROLL, MOUSE_RELATIVE, MOUSE_CURSOR };
+use { ArgsError, GenericEvent, DEFAULT_DEVICE, MOUSE_SCROLL, MOUSE_RELATIVE, MOUSE_CURSOR };
+use generic_event::try_with_legacy_args;
+
+/// The unit of a scroll delta.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq, Eq, Hash, Debug)]
+pub enum ScrollUnit {
+    /// Lines or wheel ticks, reported by most mouse wheels.
+    Lines,
+    /// Pixels, reported by touchpads with precise scrolling.
+    Pixels,
+}
+
+/// The phase of a scroll gesture on a touchpad.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq, Eq, Hash, Debug)]
+pub enum ScrollPhase {
+    /// The fingers started scrolling.
+    Begin,
+    /// The fingers moved.
+    Update,
+    /// The scroll ended, either when the fingers were lifted
+    /// without momentum, or when the momentum stopped.
+    End,
+    /// Scrolling continues by momentum after the fingers were lifted.
+    Momentum,
+}
+
+/// Scroll arguments.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq, Debug)]
+pub struct ScrollArgs {
+    /// The horizontal delta.
+    pub x: f64,
+    /// The vertical delta.
+    pub y: f64,
+    /// The unit of the delta.
+    pub unit: ScrollUnit,
+    /// The phase of the scroll gesture.
+    ///
+    /// This is `None` for mouse wheels, which do not have phases.
+    pub phase: Option<ScrollPhase>,
+    /// A unique identifier for the mouse or touchpad.
+    pub device: i64,
+}
+
+impl ScrollArgs {
+    /// Creates scroll arguments in lines, without a phase.
+    pub fn new(x: f64, y: f64) -> ScrollArgs {
+        ScrollArgs {
+            x: x,
+            y: y,
+            unit: ScrollUnit::Lines,
+            phase: None,
+            device: DEFAULT_DEVICE,
+        }
+    }
+
+    /// Sets the unit.
+    pub fn unit(mut self, unit: ScrollUnit) -> ScrollArgs {
+        self.unit = unit;
+        self
+    }
+
+    /// Sets the phase.
+    pub fn phase(mut self, phase: ScrollPhase) -> ScrollArgs {
+        self.phase = Some(phase);
+        self
+    }
+
+    /// Sets the device id.
+    pub fn device(mut self, device: i64) -> ScrollArgs {
+        self.device = device;
+        self
+    }
+}
+
+impl From<(f64, f64)> for ScrollArgs {
+    fn from((x, y): (f64, f64)) -> ScrollArgs {
+        ScrollArgs::new(x, y)
+    }
+}
+
+/// Mouse cursor arguments.
+///
+/// The cursor position is shared by all mice,
+/// the device id tells which mouse moved it when the backend reports it.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq, Debug)]
+pub struct MouseCursorArgs {
+    /// The horizontal position in window coordinates.
+    pub x: f64,
+    /// The vertical position in window coordinates.
+    pub y: f64,
+    /// A unique identifier for the mouse.
+    pub device: i64,
+}
+
+impl MouseCursorArgs {
+    /// Creates mouse cursor arguments for the default device.
+    pub fn new(x: f64, y: f64) -> MouseCursorArgs {
+        MouseCursorArgs {
+            x: x,
+            y: y,
+            device: DEFAULT_DEVICE,
+        }
+    }
+
+    /// Sets the device id.
+    pub fn device(mut self, device: i64) -> MouseCursorArgs {
+        self.device = device;
+        self
+    }
+}
+
+impl From<(f64, f64)> for MouseCursorArgs {
+    fn from((x, y): (f64, f64)) -> MouseCursorArgs {
+        MouseCursorArgs::new(x, y)
+    }
+}
+
+/// Relative mouse motion arguments.
+///
+/// The cursor position is shared by all mice,
+/// so use relative motion to tell mice apart.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq, Debug)]
+pub struct MouseRelativeArgs {
+    /// The horizontal motion.
+    pub x: f64,
+    /// The vertical motion.
+    pub y: f64,
+    /// A unique identifier for the mouse.
+    pub device: i64,
+}
+
+impl MouseRelativeArgs {
+    /// Creates relative motion arguments for the default device.
+    pub fn new(x: f64, y: f64) -> MouseRelativeArgs {
+        MouseRelativeArgs {
+            x: x,
+            y: y,
+            device: DEFAULT_DEVICE,
+        }
+    }
+
+    /// Sets the device id.
+    pub fn device(mut self, device: i64) -> MouseRelativeArgs {
+        self.device = device;
+        self
+    }
+}
+
+impl From<(f64, f64)> for MouseRelativeArgs {
+    fn from((x, y): (f64, f64)) -> MouseRelativeArgs {
+        MouseRelativeArgs::new(x, y)
+    }
+}
 
 /// Represent a mouse button.
 #[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq,
@@ -62,6 +216,34 @@ impl From<MouseButton> for u32 {
     }
 }
 
+/// Formats the mouse button with a `Mouse` prefix, for example `Mouse Left`.
+impl fmt::Display for MouseButton {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Mouse {:?}", self)
+    }
+}
+
+/// Parses a mouse button, with or without the `Mouse` prefix.
+impl FromStr for MouseButton {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<MouseButton, String> {
+        let name = if s.starts_with("Mouse ") { &s[6..] } else { s };
+        Ok(match name {
+            "Unknown" => MouseButton::Unknown,
+            "Left" => MouseButton::Left,
+            "Right" => MouseButton::Right,
+            "Middle" => MouseButton::Middle,
+            "X1" => MouseButton::X1,
+            "X2" => MouseButton::X2,
+            "Button6" => MouseButton::Button6,
+            "Button7" => MouseButton::Button7,
+            "Button8" => MouseButton::Button8,
+            _ => { return Err(format!("Unknown mouse button `{}`", s)); }
+        })
+    }
+}
+
 #[cfg(test)]
 mod mouse_button_tests {
     use super::*;
@@ -72,109 +254,168 @@ mod mouse_button_tests {
             let button: MouseButton = i.into();
             let j: u32 = button.into();
             assert_eq!(i, j);
+            assert_eq!(button.to_string().parse(), Ok(button));
         }
+        assert_eq!("Left".parse(), Ok(MouseButton::Left));
     }
 }
 
 /// The position of the mouse cursor
 pub trait MouseCursorEvent: Sized {
-    /// Creates a mouse cursor event.
-    fn from_xy(x: f64, y: f64, old_event: &Self) -> Option<Self>;
+    /// Creates a mouse cursor event for the default device.
+    fn from_xy(x: f64, y: f64, old_event: &Self) -> Option<Self> {
+        MouseCursorEvent::from_mouse_cursor_args(MouseCursorArgs::new(x, y), old_event)
+    }
+    /// Creates a mouse cursor event from cursor arguments.
+    fn from_mouse_cursor_args(args: MouseCursorArgs, old_event: &Self) -> Option<Self>;
     /// Calls closure if this is a mouse cursor event.
-    fn mouse_cursor<U, F>(&self, f: F) -> Option<U>
-        where F: FnMut(f64, f64) -> U;
+    fn mouse_cursor<U, F>(&self, mut f: F) -> Option<U>
+        where F: FnMut(f64, f64) -> U
+    {
+        self.mouse_cursor_with_args(|args| f(args.x, args.y))
+    }
+    /// Calls closure with cursor arguments if this is a mouse cursor event.
+    fn mouse_cursor_with_args<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(MouseCursorArgs) -> U;
     /// Returns mouse cursor arguments.
     fn mouse_cursor_args(&self) -> Option<[f64; 2]> {
         self.mouse_cursor(|x, y| [x, y])
     }
+    /// Returns cursor arguments, which include the device id.
+    fn mouse_cursor_full_args(&self) -> Option<MouseCursorArgs> {
+        self.mouse_cursor_with_args(|args| args)
+    }
+    /// Returns cursor arguments, or an error if they have an unexpected type.
+    fn try_mouse_cursor_full_args(&self) -> Result<Option<MouseCursorArgs>, ArgsError>;
 }
 
 impl<T: GenericEvent> MouseCursorEvent for T {
-    fn from_xy(x: f64, y: f64, old_event: &Self) -> Option<Self> {
-        GenericEvent::from_args(MOUSE_CURSOR, &(x, y) as &Any, old_event)
+    fn from_mouse_cursor_args(args: MouseCursorArgs, old_event: &Self) -> Option<Self> {
+        // Pass a plain position when possible, for implementations that expect one.
+        if args == MouseCursorArgs::new(args.x, args.y) {
+            GenericEvent::from_args(MOUSE_CURSOR, &(args.x, args.y) as &Any, old_event)
+        } else {
+            GenericEvent::from_args(MOUSE_CURSOR, &args as &Any, old_event)
+        }
     }
 
-    fn mouse_cursor<U, F>(&self, mut f: F) -> Option<U>
-        where F: FnMut(f64, f64) -> U
+    fn mouse_cursor_with_args<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(MouseCursorArgs) -> U
     {
-        if self.event_id() != MOUSE_CURSOR {
-            return None;
-        }
-        self.with_args(|any| {
-            if let Some(&(x, y)) = any.downcast_ref::<(f64, f64)>() {
-                Some(f(x, y))
-            } else {
-                panic!("Expected (f64, f64)")
-            }
-        })
+        try_with_legacy_args::<_, _, (f64, f64), _, _>(
+            self, MOUSE_CURSOR, "MouseCursorArgs", f)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_mouse_cursor_full_args(&self) -> Result<Option<MouseCursorArgs>, ArgsError> {
+        try_with_legacy_args::<_, _, (f64, f64), _, _>(
+            self, MOUSE_CURSOR, "MouseCursorArgs", |args| args)
     }
 }
 
 /// The relative movement of mouse cursor
 pub trait MouseRelativeEvent: Sized {
-    /// Creates a mouse relative event.
-    fn from_xy(x: f64, y: f64, old_event: &Self) -> Option<Self>;
+    /// Creates a mouse relative event for the default device.
+    fn from_xy(x: f64, y: f64, old_event: &Self) -> Option<Self> {
+        MouseRelativeEvent::from_mouse_relative_args(MouseRelativeArgs::new(x, y), old_event)
+    }
+    /// Creates a mouse relative event from relative motion arguments.
+    fn from_mouse_relative_args(args: MouseRelativeArgs, old_event: &Self) -> Option<Self>;
     /// Calls closure if this is a mouse relative event.
-    fn mouse_relative<U, F>(&self, f: F) -> Option<U>
-        where F: FnMut(f64, f64) -> U;
+    fn mouse_relative<U, F>(&self, mut f: F) -> Option<U>
+        where F: FnMut(f64, f64) -> U
+    {
+        self.mouse_relative_with_args(|args| f(args.x, args.y))
+    }
+    /// Calls closure with relative motion arguments if this is a mouse relative event.
+    fn mouse_relative_with_args<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(MouseRelativeArgs) -> U;
     /// Returns mouse relative arguments.
     fn mouse_relative_args(&self) -> Option<[f64; 2]> {
         self.mouse_relative(|x, y| [x, y])
     }
+    /// Returns relative motion arguments, which include the device id.
+    fn mouse_relative_full_args(&self) -> Option<MouseRelativeArgs> {
+        self.mouse_relative_with_args(|args| args)
+    }
+    /// Returns relative motion arguments, or an error if they have an unexpected type.
+    fn try_mouse_relative_full_args(&self) -> Result<Option<MouseRelativeArgs>, ArgsError>;
 }
 
 impl<T: GenericEvent> MouseRelativeEvent for T {
-    fn from_xy(x: f64, y: f64, old_event: &Self) -> Option<Self> {
-        GenericEvent::from_args(MOUSE_RELATIVE, &(x, y) as &Any, old_event)
+    fn from_mouse_relative_args(args: MouseRelativeArgs, old_event: &Self) -> Option<Self> {
+        // Pass a plain delta when possible, for implementations that expect one.
+        if args == MouseRelativeArgs::new(args.x, args.y) {
+            GenericEvent::from_args(MOUSE_RELATIVE, &(args.x, args.y) as &Any, old_event)
+        } else {
+            GenericEvent::from_args(MOUSE_RELATIVE, &args as &Any, old_event)
+        }
     }
 
-    fn mouse_relative<U, F>(&self, mut f: F) -> Option<U>
-        where F: FnMut(f64, f64) -> U
+    fn mouse_relative_with_args<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(MouseRelativeArgs) -> U
     {
-        if self.event_id() != MOUSE_RELATIVE {
-            return None;
-        }
-        self.with_args(|any| {
-            if let Some(&(x, y)) = any.downcast_ref::<(f64, f64)>() {
-                Some(f(x, y))
-            } else {
-                panic!("Expected (f64, f64)")
-            }
-        })
+        try_with_legacy_args::<_, _, (f64, f64), _, _>(
+            self, MOUSE_RELATIVE, "MouseRelativeArgs", f)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_mouse_relative_full_args(&self) -> Result<Option<MouseRelativeArgs>, ArgsError> {
+        try_with_legacy_args::<_, _, (f64, f64), _, _>(
+            self, MOUSE_RELATIVE, "MouseRelativeArgs", |args| args)
     }
 }
 
-/// The scroll of the mouse wheel
+/// The scroll of the mouse wheel or touchpad
 pub trait MouseScrollEvent: Sized {
-    /// Creates a mouse scroll event.
-    fn from_xy(x: f64, y: f64, old_event: &Self) -> Option<Self>;
+    /// Creates a mouse scroll event in lines, without a phase.
+    fn from_xy(x: f64, y: f64, old_event: &Self) -> Option<Self> {
+        MouseScrollEvent::from_scroll_args(ScrollArgs::new(x, y), old_event)
+    }
+    /// Creates a mouse scroll event from scroll arguments.
+    fn from_scroll_args(args: ScrollArgs, old_event: &Self) -> Option<Self>;
     /// Calls a closure if this is a mouse scroll event.
-    fn mouse_scroll<U, F>(&self, f: F) -> Option<U>
-        where F: FnMut(f64, f64) -> U;
+    fn mouse_scroll<U, F>(&self, mut f: F) -> Option<U>
+        where F: FnMut(f64, f64) -> U
+    {
+        self.mouse_scroll_with_args(|args| f(args.x, args.y))
+    }
+    /// Calls a closure with scroll arguments if this is a mouse scroll event.
+    fn mouse_scroll_with_args<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(ScrollArgs) -> U;
     /// Returns mouse scroll arguments.
     fn mouse_scroll_args(&self) -> Option<[f64; 2]> {
         self.mouse_scroll(|x, y| [x, y])
     }
+    /// Returns scroll arguments, which include the unit and phase.
+    fn mouse_scroll_full_args(&self) -> Option<ScrollArgs> {
+        self.mouse_scroll_with_args(|args| args)
+    }
+    /// Returns scroll arguments, or an error if they have an unexpected type.
+    fn try_mouse_scroll_full_args(&self) -> Result<Option<ScrollArgs>, ArgsError>;
 }
 
 impl<T: GenericEvent> MouseScrollEvent for T {
-    fn from_xy(x: f64, y: f64, old_event: &Self) -> Option<Self> {
-        GenericEvent::from_args(MOUSE_SCROLL, &(x, y) as &Any, old_event)
+    fn from_scroll_args(args: ScrollArgs, old_event: &Self) -> Option<Self> {
+        // Pass a plain delta when possible, for implementations that expect one.
+        if args == ScrollArgs::new(args.x, args.y) {
+            GenericEvent::from_args(MOUSE_SCROLL, &(args.x, args.y) as &Any, old_event)
+        } else {
+            GenericEvent::from_args(MOUSE_SCROLL, &args as &Any, old_event)
+        }
     }
 
-    fn mouse_scroll<U, F>(&self, mut f: F) -> Option<U>
-        where F: FnMut(f64, f64) -> U
+    fn mouse_scroll_with_args<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(ScrollArgs) -> U
     {
-        if self.event_id() != MOUSE_SCROLL {
-            return None;
-        }
-        self.with_args(|any| {
-            if let Some(&(x, y)) = any.downcast_ref::<(f64, f64)>() {
-                Some(f(x, y))
-            } else {
-                panic!("Expected (f64, f64)")
-            }
-        })
+        try_with_legacy_args::<_, _, (f64, f64), _, _>(
+            self, MOUSE_SCROLL, "ScrollArgs", f)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_mouse_scroll_full_args(&self) -> Result<Option<ScrollArgs>, ArgsError> {
+        try_with_legacy_args::<_, _, (f64, f64), _, _>(
+            self, MOUSE_SCROLL, "ScrollArgs", |args| args)
     }
 }
 
@@ -186,7 +427,7 @@ mod mouse_event_tests {
     fn test_input_mouse_cursor() {
         use super::super::{ Input, Motion };
 
-        let e = Input::Move(Motion::MouseCursor(0.0, 0.0));
+        let e = Input::Move(Motion::MouseCursor((0.0, 0.0).into()));
         let a: Option<Input> = MouseCursorEvent::from_xy(1.0, 0.0, &e);
         let b: Option<Input> = a.clone().unwrap().mouse_cursor(|x, y|
             MouseCursorEvent::from_xy(x, y, a.as_ref().unwrap())).unwrap();
@@ -198,18 +439,29 @@ mod mouse_event_tests {
         use Event;
         use super::super::{ Input, Motion };
 
-        let e = Event::Input(Input::Move(Motion::MouseCursor(0.0, 0.0)));
+        let e = Event::Input(Input::Move(Motion::MouseCursor((0.0, 0.0).into())));
         let a: Option<Event> = MouseCursorEvent::from_xy(1.0, 0.0, &e);
         let b: Option<Event> = a.clone().unwrap().mouse_cursor(|x, y|
             MouseCursorEvent::from_xy(x, y, a.as_ref().unwrap())).unwrap();
         assert_eq!(a, b);
     }
 
+    #[test]
+    fn test_input_mouse_cursor_device() {
+        use super::super::{ Input, Motion };
+
+        let e = Input::Move(Motion::MouseCursor((0.0, 0.0).into()));
+        let args = MouseCursorArgs::new(1.0, 2.0).device(3);
+        let a: Option<Input> = MouseCursorEvent::from_mouse_cursor_args(args, &e);
+        assert_eq!(a.as_ref().unwrap().mouse_cursor_full_args(), Some(args));
+        assert_eq!(a.as_ref().unwrap().mouse_cursor_args(), Some([1.0, 2.0]));
+    }
+
     #[test]
     fn test_input_mouse_relative() {
         use super::super::{ Input, Motion };
 
-        let e = Input::Move(Motion::MouseRelative(0.0, 0.0));
+        let e = Input::Move(Motion::MouseRelative(MouseRelativeArgs::new(0.0, 0.0)));
         let a: Option<Input> = MouseRelativeEvent::from_xy(1.0, 0.0, &e);
         let b: Option<Input> = a.clone().unwrap().mouse_relative(|x, y|
             MouseRelativeEvent::from_xy(x, y, a.as_ref().unwrap())).unwrap();
@@ -221,7 +473,7 @@ mod mouse_event_tests {
         use Event;
         use super::super::{ Input, Motion };
 
-        let e = Event::Input(Input::Move(Motion::MouseRelative(0.0, 0.0)));
+        let e = Event::Input(Input::Move(Motion::MouseRelative(MouseRelativeArgs::new(0.0, 0.0))));
         let a: Option<Event> = MouseRelativeEvent::from_xy(1.0, 0.0, &e);
         let b: Option<Event> = a.clone().unwrap().mouse_relative(|x, y|
             MouseRelativeEvent::from_xy(x, y, a.as_ref().unwrap())).unwrap();
@@ -232,7 +484,7 @@ mod mouse_event_tests {
     fn test_input_mouse_scroll() {
         use super::super::{ Input, Motion };
 
-        let e = Input::Move(Motion::MouseScroll(0.0, 0.0));
+        let e = Input::Move(Motion::MouseScroll(ScrollArgs::new(0.0, 0.0)));
         let a: Option<Input> = MouseScrollEvent::from_xy(1.0, 0.0, &e);
         let b: Option<Input> = a.clone().unwrap().mouse_scroll(|x, y|
             MouseScrollEvent::from_xy(x, y, a.as_ref().unwrap())).unwrap();
@@ -244,10 +496,34 @@ mod mouse_event_tests {
         use Event;
         use super::super::{ Input, Motion };
 
-        let e = Event::Input(Input::Move(Motion::MouseScroll(0.0, 0.0)));
+        let e = Event::Input(Input::Move(Motion::MouseScroll(ScrollArgs::new(0.0, 0.0))));
         let a: Option<Event> = MouseScrollEvent::from_xy(1.0, 0.0, &e);
         let b: Option<Event> = a.clone().unwrap().mouse_scroll(|x, y|
             MouseScrollEvent::from_xy(x, y, a.as_ref().unwrap())).unwrap();
         assert_eq!(a, b);
     }
+
+    #[test]
+    fn test_input_mouse_scroll_args() {
+        use super::super::{ Input, Motion };
+
+        let e = Input::Move(Motion::MouseScroll(ScrollArgs::new(0.0, 0.0)));
+        let args = ScrollArgs::new(0.0, 12.5)
+            .unit(ScrollUnit::Pixels)
+            .phase(ScrollPhase::Momentum);
+        let a: Option<Input> = MouseScrollEvent::from_scroll_args(args, &e);
+        assert_eq!(a.as_ref().unwrap().mouse_scroll_full_args(), Some(args));
+        assert_eq!(a.as_ref().unwrap().mouse_scroll_args(), Some([0.0, 12.5]));
+    }
+
+    #[test]
+    fn test_input_mouse_relative_device() {
+        use super::super::{ Input, Motion };
+
+        let e = Input::Move(Motion::MouseRelative(MouseRelativeArgs::new(0.0, 0.0)));
+        let args = MouseRelativeArgs::new(2.0, -1.0).device(1);
+        let a: Option<Input> = MouseRelativeEvent::from_mouse_relative_args(args, &e);
+        assert_eq!(a.as_ref().unwrap().mouse_relative_full_args(), Some(args));
+        assert_eq!(a.as_ref().unwrap().mouse_relative_args(), Some([2.0, -1.0]));
+    }
 }
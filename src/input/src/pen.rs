@@ -0,0 +1,188 @@
+use std::any::Any;
+
+use { ArgsError, GenericEvent, PEN };
+use generic_event::try_with_args;
+
+/// The end of the pen in use.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq, Eq, Hash, Debug)]
+pub enum PenTool {
+    /// The tip of the pen.
+    Pen,
+    /// The eraser, usually at the other end of the pen.
+    Eraser,
+}
+
+/// Pen arguments, for example from a graphics tablet.
+///
+/// - Coordinates are in window coordinates, like the mouse cursor
+/// - Angles are in radians
+/// - The pen is in range while hovering above the tablet or touching it
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq, Debug)]
+pub struct PenArgs {
+    /// A unique identifier for the pen device.
+    pub device: i64,
+    /// The x coordinate of the pen position.
+    pub x: f64,
+    /// The y coordinate of the pen position.
+    pub y: f64,
+    /// The pressure of the tip, normalized 0..1.
+    ///
+    /// This is zero while hovering.
+    pub pressure: f64,
+    /// The angle between the pen and the tablet normal in the x direction.
+    pub tilt_x: f64,
+    /// The angle between the pen and the tablet normal in the y direction.
+    pub tilt_y: f64,
+    /// The rotation of the pen around its own axis.
+    pub rotation: f64,
+    /// Whether the pen is close enough to the tablet to be detected.
+    ///
+    /// When the pen leaves the range, a last event is sent with this set to `false`.
+    pub in_range: bool,
+    /// Whether the pen touches the tablet.
+    pub contact: bool,
+    /// The end of the pen in use.
+    pub tool: PenTool,
+    /// The barrel buttons held down, one bit per button starting at the lowest.
+    ///
+    /// Barrel buttons only appear as this bitmask in pen events,
+    /// they do not generate press or release events.
+    pub buttons: u8,
+}
+
+impl PenArgs {
+    /// Creates pen arguments for the tip touching the tablet
+    /// without tilt, rotation or buttons.
+    pub fn new(device: i64, pos: [f64; 2], pressure: f64) -> PenArgs {
+        PenArgs {
+            device: device,
+            x: pos[0],
+            y: pos[1],
+            pressure: pressure,
+            tilt_x: 0.0,
+            tilt_y: 0.0,
+            rotation: 0.0,
+            in_range: true,
+            contact: true,
+            tool: PenTool::Pen,
+            buttons: 0,
+        }
+    }
+
+    /// Creates pen arguments for the tip hovering above the tablet.
+    pub fn hover(device: i64, pos: [f64; 2]) -> PenArgs {
+        PenArgs {
+            contact: false,
+            ..PenArgs::new(device, pos, 0.0)
+        }
+    }
+
+    /// Sets the tilt in x and y direction.
+    pub fn tilt(mut self, tilt: [f64; 2]) -> PenArgs {
+        self.tilt_x = tilt[0];
+        self.tilt_y = tilt[1];
+        self
+    }
+
+    /// Sets the rotation.
+    pub fn rotation(mut self, rotation: f64) -> PenArgs {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Sets the end of the pen in use.
+    pub fn tool(mut self, tool: PenTool) -> PenArgs {
+        self.tool = tool;
+        self
+    }
+
+    /// Sets the barrel buttons held down.
+    pub fn buttons(mut self, buttons: u8) -> PenArgs {
+        self.buttons = buttons;
+        self
+    }
+
+    /// The position of the pen.
+    pub fn position(&self) -> [f64; 2] {
+        [self.x, self.y]
+    }
+
+    /// The tilt in x and y direction.
+    pub fn tilt_xy(&self) -> [f64; 2] {
+        [self.tilt_x, self.tilt_y]
+    }
+
+    /// Returns true if the barrel button is held down, starting at 0.
+    pub fn is_button_down(&self, button: u8) -> bool {
+        button < 8 && self.buttons & (1 << button) != 0
+    }
+}
+
+/// When a pen moves, touches or leaves the tablet, or its buttons change.
+pub trait PenEvent: Sized {
+    /// Creates a pen event.
+    fn from_pen_args(args: &PenArgs, old_event: &Self) -> Option<Self>;
+    /// Calls closure if this is a pen event.
+    fn pen<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(&PenArgs) -> U;
+    /// Returns pen arguments.
+    fn pen_args(&self) -> Option<PenArgs> {
+        self.pen(|args| args.clone())
+    }
+    /// Returns pen arguments, or an error if they have an unexpected type.
+    fn try_pen_args(&self) -> Result<Option<PenArgs>, ArgsError>;
+}
+
+impl<T> PenEvent for T where T: GenericEvent {
+    fn from_pen_args(args: &PenArgs, old_event: &Self) -> Option<Self> {
+        GenericEvent::from_args(PEN, args as &Any, old_event)
+    }
+
+    fn pen<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(&PenArgs) -> U
+    {
+        try_with_args(self, PEN, "PenArgs", f)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_pen_args(&self) -> Result<Option<PenArgs>, ArgsError> {
+        try_with_args(self, PEN, "PenArgs", |&args: &PenArgs| args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_pen() {
+        use super::super::{ Input, Motion };
+
+        let e = Input::Move(Motion::Pen(PenArgs::hover(0, [0.0; 2])));
+        let args = PenArgs::new(0, [10.0, 20.0], 0.5)
+            .tilt([0.2, -0.1])
+            .rotation(1.0)
+            .tool(PenTool::Eraser)
+            .buttons(0b10);
+        let a: Option<Input> = PenEvent::from_pen_args(&args, &e);
+        let b: Option<Input> = a.clone().unwrap().pen(|p|
+            PenEvent::from_pen_args(p, a.as_ref().unwrap())).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.unwrap().pen_args(), Some(args));
+        assert!(args.is_button_down(1));
+        assert!(!args.is_button_down(0));
+    }
+
+    #[test]
+    fn test_event_pen() {
+        use Event;
+        use super::super::{ Input, Motion };
+
+        let e = Event::Input(Input::Move(Motion::Pen(PenArgs::hover(0, [0.0; 2]))));
+        let a: Option<Event> = PenEvent::from_pen_args(&PenArgs::hover(1, [5.0, 5.0]), &e);
+        let b: Option<Event> = a.clone().unwrap().pen(|p|
+            PenEvent::from_pen_args(&PenArgs::hover(p.device, p.position()),
+                                    a.as_ref().unwrap())).unwrap();
+        assert_eq!(a, b);
+    }
+}
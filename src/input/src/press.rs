@@ -1,38 +1,56 @@
 use std::any::Any;
 
-use { Button, GenericEvent, PRESS };
+use { ArgsError, Button, ButtonArgs, GenericEvent, PRESS };
+use generic_event::try_with_legacy_args;
 
 /// The press of a button
 pub trait PressEvent: Sized {
     /// Creates a press event.
-    fn from_button(button: Button, old_event: &Self) -> Option<Self>;
+    fn from_button(button: Button, old_event: &Self) -> Option<Self> {
+        PressEvent::from_button_args(button.into(), old_event)
+    }
+    /// Creates a press event from button arguments.
+    fn from_button_args(args: ButtonArgs, old_event: &Self) -> Option<Self>;
     /// Calls closure if this is a press event.
-    fn press<U, F>(&self, f: F) -> Option<U>
-        where F: FnMut(Button) -> U;
+    fn press<U, F>(&self, mut f: F) -> Option<U>
+        where F: FnMut(Button) -> U
+    {
+        self.press_with_args(|args| f(args.button))
+    }
+    /// Calls closure with button arguments if this is a press event.
+    fn press_with_args<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(ButtonArgs) -> U;
     /// Returns press arguments.
     fn press_args(&self) -> Option<Button> {
         self.press(|button| button)
     }
+    /// Returns press button arguments, which include the scancode.
+    fn press_button_args(&self) -> Option<ButtonArgs> {
+        self.press_with_args(|args| args)
+    }
+    /// Returns press button arguments, or an error if they have an unexpected type.
+    fn try_press_button_args(&self) -> Result<Option<ButtonArgs>, ArgsError>;
 }
 
 impl<T: GenericEvent> PressEvent for T {
-    fn from_button(button: Button, old_event: &Self) -> Option<Self> {
-        GenericEvent::from_args(PRESS, &button as &Any, old_event)
+    fn from_button_args(args: ButtonArgs, old_event: &Self) -> Option<Self> {
+        // Pass a plain button when possible, for implementations that expect one.
+        if args == ButtonArgs::new(args.button) {
+            GenericEvent::from_args(PRESS, &args.button as &Any, old_event)
+        } else {
+            GenericEvent::from_args(PRESS, &args as &Any, old_event)
+        }
     }
 
-    fn press<U, F>(&self, mut f: F) -> Option<U>
-        where F: FnMut(Button) -> U
+    fn press_with_args<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(ButtonArgs) -> U
     {
-        if self.event_id() != PRESS {
-            return None;
-        }
-        self.with_args(|any| {
-            if let Some(&button) = any.downcast_ref::<Button>() {
-                Some(f(button))
-            } else {
-                panic!("Expected Button")
-            }
-        })
+        try_with_legacy_args::<_, _, Button, _, _>(self, PRESS, "ButtonArgs", f)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_press_button_args(&self) -> Result<Option<ButtonArgs>, ArgsError> {
+        try_with_legacy_args::<_, _, Button, _, _>(self, PRESS, "ButtonArgs", |args| args)
     }
 }
 
@@ -44,7 +62,7 @@ mod tests {
     fn test_input_press() {
         use super::super::{ Button, Key, Input };
 
-        let e = Input::Press(Button::Keyboard(Key::S));
+        let e = Input::Press(Button::Keyboard(Key::S).into());
         let button = Button::Keyboard(Key::A);
         let x: Option<Input> = PressEvent::from_button(button, &e);
         let y: Option<Input> = x.clone().unwrap().press(|button|
@@ -58,11 +76,22 @@ mod tests {
         use Event;
         use super::super::{ Button, Key, Input };
 
-        let e = Event::Input(Input::Press(Button::Keyboard(Key::S)));
+        let e = Event::Input(Input::Press(Button::Keyboard(Key::S).into()));
         let button = Button::Keyboard(Key::A);
         let x: Option<Event> = PressEvent::from_button(button, &e);
         let y: Option<Event> = x.clone().unwrap().press(|button|
             PressEvent::from_button(button, x.as_ref().unwrap())).unwrap();
         assert_eq!(x, y);
     }
+
+    #[test]
+    fn test_input_press_scancode() {
+        use super::super::{ Button, ButtonArgs, Key, Input, Scancode };
+
+        let e = Input::Press(Button::Keyboard(Key::S).into());
+        let args = ButtonArgs::new(Button::Keyboard(Key::Z)).scancode(Scancode::W).device(1);
+        let x: Option<Input> = PressEvent::from_button_args(args, &e);
+        assert_eq!(x.as_ref().unwrap().press_button_args(), Some(args));
+        assert_eq!(x.as_ref().unwrap().press_args(), Some(Button::Keyboard(Key::Z)));
+    }
 }
@@ -1,38 +1,56 @@
 use std::any::Any;
 
-use { Button, GenericEvent, RELEASE };
+use { ArgsError, Button, ButtonArgs, GenericEvent, RELEASE };
+use generic_event::try_with_legacy_args;
 
 /// The release of a button
 pub trait ReleaseEvent: Sized {
     /// Creates a release event.
-    fn from_button(button: Button, old_event: &Self) -> Option<Self>;
+    fn from_button(button: Button, old_event: &Self) -> Option<Self> {
+        ReleaseEvent::from_button_args(button.into(), old_event)
+    }
+    /// Creates a release event from button arguments.
+    fn from_button_args(args: ButtonArgs, old_event: &Self) -> Option<Self>;
     /// Calls closure if this is a release event.
-    fn release<U, F>(&self, f: F) -> Option<U>
-        where F: FnMut(Button) -> U;
+    fn release<U, F>(&self, mut f: F) -> Option<U>
+        where F: FnMut(Button) -> U
+    {
+        self.release_with_args(|args| f(args.button))
+    }
+    /// Calls closure with button arguments if this is a release event.
+    fn release_with_args<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(ButtonArgs) -> U;
     /// Returns release arguments.
     fn release_args(&self) -> Option<Button> {
         self.release(|button| button)
     }
+    /// Returns release button arguments, which include the scancode.
+    fn release_button_args(&self) -> Option<ButtonArgs> {
+        self.release_with_args(|args| args)
+    }
+    /// Returns release button arguments, or an error if they have an unexpected type.
+    fn try_release_button_args(&self) -> Result<Option<ButtonArgs>, ArgsError>;
 }
 
 impl<T: GenericEvent> ReleaseEvent for T {
-    fn from_button(button: Button, old_event: &Self) -> Option<Self> {
-        GenericEvent::from_args(RELEASE, &button as &Any, old_event)
+    fn from_button_args(args: ButtonArgs, old_event: &Self) -> Option<Self> {
+        // Pass a plain button when possible, for implementations that expect one.
+        if args == ButtonArgs::new(args.button) {
+            GenericEvent::from_args(RELEASE, &args.button as &Any, old_event)
+        } else {
+            GenericEvent::from_args(RELEASE, &args as &Any, old_event)
+        }
     }
 
-    fn release<U, F>(&self, mut f: F) -> Option<U>
-        where F: FnMut(Button) -> U
+    fn release_with_args<U, F>(&self, f: F) -> Option<U>
+        where F: FnMut(ButtonArgs) -> U
     {
-        if self.event_id() != RELEASE {
-            return None;
-        }
-        self.with_args(|any| {
-            if let Some(&button) = any.downcast_ref::<Button>() {
-                Some(f(button))
-            } else {
-                panic!("Expected Button")
-            }
-        })
+        try_with_legacy_args::<_, _, Button, _, _>(self, RELEASE, "ButtonArgs", f)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_release_button_args(&self) -> Result<Option<ButtonArgs>, ArgsError> {
+        try_with_legacy_args::<_, _, Button, _, _>(self, RELEASE, "ButtonArgs", |args| args)
     }
 }
 
@@ -45,7 +63,7 @@ mod tests {
     fn test_input_release() {
         use super::super::{ Button, Key, Input };
 
-        let e = Input::Release(Button::Keyboard(Key::S));
+        let e = Input::Release(Button::Keyboard(Key::S).into());
         let button = Button::Keyboard(Key::A);
         let x: Option<Input> = ReleaseEvent::from_button(button, &e);
         let y: Option<Input> = x.clone().unwrap().release(|button|
@@ -58,11 +76,22 @@ mod tests {
         use Event;
         use super::super::{ Button, Key, Input };
 
-        let e = Event::Input(Input::Release(Button::Keyboard(Key::S)));
+        let e = Event::Input(Input::Release(Button::Keyboard(Key::S).into()));
         let button = Button::Keyboard(Key::A);
         let x: Option<Event> = ReleaseEvent::from_button(button, &e);
         let y: Option<Event> = x.clone().unwrap().release(|button|
             ReleaseEvent::from_button(button, x.as_ref().unwrap())).unwrap();
         assert_eq!(x, y);
     }
+
+    #[test]
+    fn test_input_release_scancode() {
+        use super::super::{ Button, ButtonArgs, Key, Input, Scancode };
+
+        let e = Input::Release(Button::Keyboard(Key::S).into());
+        let args = ButtonArgs::new(Button::Keyboard(Key::Z)).scancode(Scancode::W);
+        let x: Option<Input> = ReleaseEvent::from_button_args(args, &e);
+        assert_eq!(x.as_ref().unwrap().release_button_args(), Some(args));
+        assert_eq!(x.as_ref().unwrap().release_args(), Some(Button::Keyboard(Key::Z)));
+    }
 }
@@ -1,7 +1,8 @@
 use std::any::Any;
 use viewport::Viewport;
 
-use { GenericEvent, RENDER };
+use { ArgsError, GenericEvent, RENDER };
+use generic_event::try_with_args;
 
 /// Render arguments
 #[derive(Copy, Clone, PartialEq, Debug)]
@@ -40,6 +41,8 @@ pub trait RenderEvent: Sized {
     fn render_args(&self) -> Option<RenderArgs> {
         self.render(|args| args.clone())
     }
+    /// Returns render arguments, or an error if they have an unexpected type.
+    fn try_render_args(&self) -> Result<Option<RenderArgs>, ArgsError>;
 }
 
 impl<T: GenericEvent> RenderEvent for T {
@@ -47,19 +50,15 @@ impl<T: GenericEvent> RenderEvent for T {
         GenericEvent::from_args(RENDER, args as &Any, old_event)
     }
 
-    fn render<U, F>(&self, mut f: F) -> Option<U>
+    fn render<U, F>(&self, f: F) -> Option<U>
         where F: FnMut(&RenderArgs) -> U
     {
-        if self.event_id() != RENDER {
-            return None;
-        }
-        self.with_args(|any| {
-            if let Some(args) = any.downcast_ref::<RenderArgs>() {
-                Some(f(args))
-            } else {
-                panic!("Expected RenderArgs")
-            }
-        })
+        try_with_args(self, RENDER, "RenderArgs", f)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_render_args(&self) -> Result<Option<RenderArgs>, ArgsError> {
+        try_with_args(self, RENDER, "RenderArgs", |&args: &RenderArgs| args)
     }
 }
 
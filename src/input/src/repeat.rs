@@ -0,0 +1,139 @@
+//! Generates key repeats with a configurable delay and rate.
+//!
+//! Backends report auto-repeated key presses using the settings
+//! of the operating system, which games can not change.
+//! A `KeyRepeat` suppresses the repeats from the backend
+//! and generates its own, driven by the time of update events.
+
+use std::default::Default;
+
+use { Button, ButtonArgs, GenericEvent };
+
+/// Replaces the key repeats of the backend with its own.
+///
+/// Only the last key pressed is repeated, like on most operating systems.
+/// Generated press events have `repeat` set to `true`.
+#[derive(Clone, Debug)]
+pub struct KeyRepeat<E> {
+    /// The time in seconds before the first repeat.
+    pub delay: f64,
+    /// The number of repeats per second after the first.
+    ///
+    /// When zero, only the first repeat is generated.
+    pub rate: f64,
+    // The press event of the held key, used to create repeats.
+    held: Option<(ButtonArgs, E)>,
+    // The time until the next repeat.
+    next: f64,
+}
+
+impl<E: GenericEvent + Clone> KeyRepeat<E> {
+    /// Creates a new key repeat filter.
+    ///
+    /// The default delay is 0.5 seconds and the default rate is 30 repeats per second.
+    pub fn new() -> KeyRepeat<E> {
+        KeyRepeat {
+            delay: 0.5,
+            rate: 30.0,
+            held: None,
+            next: 0.0,
+        }
+    }
+
+    /// Sets the delay before the first repeat.
+    pub fn delay(mut self, delay: f64) -> KeyRepeat<E> {
+        self.delay = delay;
+        self
+    }
+
+    /// Sets the number of repeats per second.
+    pub fn rate(mut self, rate: f64) -> KeyRepeat<E> {
+        self.rate = rate;
+        self
+    }
+
+    /// Filters an event.
+    ///
+    /// Calls closure for the event, unless it is a repeat from the backend.
+    /// On update events, a generated repeat is passed to the closure
+    /// before the update event.
+    /// At most one repeat is generated per update,
+    /// so a long frame does not cause a burst of repeats.
+    /// When the window loses focus, repeating stops.
+    /// Events with arguments of an unexpected type are ignored.
+    pub fn event<F>(&mut self, e: &E, mut f: F)
+        where F: FnMut(E)
+    {
+        use { FocusEvent, PressEvent, ReleaseEvent, UpdateEvent };
+
+        if let Ok(Some(args)) = e.try_press_button_args() {
+            if let Button::Keyboard(_) = args.button {
+                if args.repeat { return; }
+                self.held = Some((args, e.clone()));
+                self.next = self.delay;
+            }
+        }
+        if let Ok(Some(release)) = e.try_release_button_args() {
+            let stop = match self.held {
+                Some((args, _)) => args.button == release.button,
+                None => false
+            };
+            if stop { self.held = None; }
+        }
+        if let Ok(Some(false)) = e.try_focus_args() {
+            self.held = None;
+        }
+        if let Ok(Some(args)) = e.try_update_args() {
+            if let Some((held, ref press)) = self.held {
+                self.next -= args.dt;
+                if self.next <= 0.0 {
+                    if let Some(repeat) = PressEvent::from_button_args(
+                        held.repeat(true), press) {
+                        f(repeat);
+                    }
+                    if self.rate <= 0.0 {
+                        self.next = ::std::f64::INFINITY;
+                    } else {
+                        self.next += 1.0 / self.rate;
+                        // Skip repeats missed during a long frame.
+                        if self.next <= 0.0 { self.next = 1.0 / self.rate; }
+                    }
+                }
+            }
+        }
+        f(e.clone());
+    }
+}
+
+impl<E: GenericEvent + Clone> Default for KeyRepeat<E> {
+    fn default() -> KeyRepeat<E> { KeyRepeat::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_repeat() {
+        use { Button, ButtonArgs, Event, Input, Key, PressEvent, UpdateArgs };
+
+        let press = ButtonArgs::new(Button::Keyboard(Key::A));
+        let mut repeat = KeyRepeat::new().delay(0.5).rate(10.0);
+        let mut presses = vec![];
+        {
+            let mut e = |e: &Event| repeat.event(e, |e|
+                if let Some(args) = e.press_button_args() { presses.push(args.repeat) });
+            e(&Event::Input(Input::Press(press)));
+            e(&Event::Input(Input::Press(press.repeat(true))));
+            e(&Event::Update(UpdateArgs { dt: 0.4 }));
+            e(&Event::Update(UpdateArgs { dt: 0.35 }));
+            e(&Event::Update(UpdateArgs { dt: 0.05 }));
+            e(&Event::Update(UpdateArgs { dt: 0.05 }));
+            e(&Event::Input(Input::Release(press)));
+            e(&Event::Update(UpdateArgs { dt: 1.0 }));
+        }
+        // One fresh press, one repeat for the long frame at 0.75 seconds,
+        // and the next a full interval later at 0.85 seconds.
+        assert_eq!(presses, vec![false, true, true]);
+    }
+}
@@ -1,6 +1,7 @@
 use std::any::Any;
 
-use { GenericEvent, RESIZE };
+use { ArgsError, GenericEvent, RESIZE };
+use generic_event::try_with_args;
 
 /// When the window is resized
 pub trait ResizeEvent: Sized {
@@ -13,6 +14,8 @@ pub trait ResizeEvent: Sized {
     fn resize_args(&self) -> Option<[u32; 2]> {
         self.resize(|x, y| [x, y])
     }
+    /// Returns resize arguments, or an error if they have an unexpected type.
+    fn try_resize_args(&self) -> Result<Option<[u32; 2]>, ArgsError>;
 }
 
 impl<T: GenericEvent> ResizeEvent for T {
@@ -23,16 +26,12 @@ impl<T: GenericEvent> ResizeEvent for T {
     fn resize<U, F>(&self, mut f: F) -> Option<U>
         where F: FnMut(u32, u32) -> U
     {
-        if self.event_id() != RESIZE {
-            return None;
-        }
-        self.with_args(|any| {
-            if let Some(&(w, h)) = any.downcast_ref::<(u32, u32)>() {
-                Some(f(w, h))
-            } else {
-                panic!("Expected (u32, u32)")
-            }
-        })
+        try_with_args(self, RESIZE, "(u32, u32)", |&(w, h): &(u32, u32)| f(w, h))
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_resize_args(&self) -> Result<Option<[u32; 2]>, ArgsError> {
+        try_with_args(self, RESIZE, "(u32, u32)", |&(w, h): &(u32, u32)| [w, h])
     }
 }
 
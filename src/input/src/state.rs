@@ -0,0 +1,290 @@
+//! Tracks the state of user input.
+
+use std::collections::{ HashMap, HashSet };
+use std::default::Default;
+use std::mem;
+
+use { Button, GenericEvent };
+
+/// Stores the state of buttons, mouse cursor and focus.
+///
+/// Call `event` for every event before handling it.
+/// Buttons that are held down, the cursor position and focus
+/// are updated immediately.
+/// Presses, releases, scroll and relative motion are collected
+/// until the next update event, and then stay the same until the update after.
+/// This means the queries in an update handler describe what happened
+/// since the previous update.
+///
+/// A button is held down while any device holds it,
+/// for example the same key on two keyboards.
+#[derive(Clone, Debug)]
+pub struct InputState {
+    down: HashSet<Button>,
+    // The devices holding each button down.
+    devices: HashMap<Button, HashSet<i64>>,
+    pressed: HashSet<Button>,
+    released: HashSet<Button>,
+    next_pressed: HashSet<Button>,
+    next_released: HashSet<Button>,
+    cursor: Option<[f64; 2]>,
+    scroll: [f64; 2],
+    next_scroll: [f64; 2],
+    relative: [f64; 2],
+    next_relative: [f64; 2],
+    focus: bool,
+}
+
+impl InputState {
+    /// Creates a new input state.
+    ///
+    /// The window is assumed to have focus.
+    pub fn new() -> InputState {
+        InputState {
+            down: HashSet::new(),
+            devices: HashMap::new(),
+            pressed: HashSet::new(),
+            released: HashSet::new(),
+            next_pressed: HashSet::new(),
+            next_released: HashSet::new(),
+            cursor: None,
+            scroll: [0.0; 2],
+            next_scroll: [0.0; 2],
+            relative: [0.0; 2],
+            next_relative: [0.0; 2],
+            focus: true,
+        }
+    }
+
+    /// Change input state depending on event.
+    ///
+    /// When the window loses focus, all buttons are released.
+    /// When a controller is disconnected, its buttons are released.
+    /// Events with arguments of an unexpected type are ignored.
+    pub fn event<E: GenericEvent>(&mut self, e: &E) {
+        use { ControllerConnection, ControllerConnectionEvent, FocusEvent,
+              MouseCursorEvent, MouseRelativeEvent, MouseScrollEvent,
+              PressEvent, ReleaseEvent, UpdateEvent };
+
+        if let Ok(Some(args)) = e.try_press_button_args() {
+            let button = args.button;
+            self.devices.entry(button).or_insert(HashSet::new()).insert(args.device);
+            // Ignore repeated presses while the button is held.
+            if self.down.insert(button) {
+                self.next_pressed.insert(button);
+            }
+        }
+        if let Ok(Some(args)) = e.try_release_button_args() {
+            let button = args.button;
+            let released = match self.devices.get_mut(&button) {
+                Some(devices) => {
+                    devices.remove(&args.device);
+                    devices.is_empty()
+                }
+                None => true,
+            };
+            if released {
+                self.devices.remove(&button);
+                if self.down.remove(&button) {
+                    self.next_released.insert(button);
+                }
+            }
+        }
+        if let Ok(Some(args)) = e.try_mouse_cursor_full_args() {
+            self.cursor = Some([args.x, args.y]);
+        }
+        if let Ok(Some(d)) = e.try_mouse_relative_full_args() {
+            self.next_relative[0] += d.x;
+            self.next_relative[1] += d.y;
+        }
+        if let Ok(Some(d)) = e.try_mouse_scroll_full_args() {
+            self.next_scroll[0] += d.x;
+            self.next_scroll[1] += d.y;
+        }
+        if let Ok(Some(focus)) = e.try_focus_args() {
+            self.focus = focus;
+            if !focus {
+                self.devices.clear();
+                for button in self.down.drain() {
+                    self.next_released.insert(button);
+                }
+            }
+        }
+        if let Ok(Some(ControllerConnection::Disconnected { id })) =
+            e.try_controller_connection_args() {
+            let buttons: Vec<Button> = self.down.iter().filter(|button|
+                match **button {
+                    Button::Controller(b) => b.id == id,
+                    _ => false
+                }).map(|&button| button).collect();
+            for button in buttons {
+                self.down.remove(&button);
+                self.devices.remove(&button);
+                self.next_released.insert(button);
+            }
+        }
+        if let Ok(Some(_)) = e.try_update_args() {
+            self.pressed = mem::replace(&mut self.next_pressed, HashSet::new());
+            self.released = mem::replace(&mut self.next_released, HashSet::new());
+            self.scroll = mem::replace(&mut self.next_scroll, [0.0; 2]);
+            self.relative = mem::replace(&mut self.next_relative, [0.0; 2]);
+        }
+    }
+
+    /// Returns true if the button is held down.
+    pub fn is_down<B: Into<Button>>(&self, button: B) -> bool {
+        self.down.contains(&button.into())
+    }
+
+    /// Returns true if the button was pressed since the previous update.
+    pub fn just_pressed<B: Into<Button>>(&self, button: B) -> bool {
+        self.pressed.contains(&button.into())
+    }
+
+    /// Returns true if the button was released since the previous update.
+    pub fn just_released<B: Into<Button>>(&self, button: B) -> bool {
+        self.released.contains(&button.into())
+    }
+
+    /// Returns the buttons that are held down.
+    pub fn buttons_down(&self) -> &HashSet<Button> {
+        &self.down
+    }
+
+    /// Returns the last known position of the mouse cursor.
+    pub fn cursor(&self) -> Option<[f64; 2]> {
+        self.cursor
+    }
+
+    /// Returns the scroll accumulated since the previous update.
+    ///
+    /// Deltas are summed regardless of their unit,
+    /// use `MouseScrollEvent::mouse_scroll_full_args` to handle lines and pixels separately.
+    pub fn scroll(&self) -> [f64; 2] {
+        self.scroll
+    }
+
+    /// Returns the relative mouse motion accumulated since the previous update.
+    pub fn relative(&self) -> [f64; 2] {
+        self.relative
+    }
+
+    /// Returns true if the window has focus.
+    pub fn focus(&self) -> bool {
+        self.focus
+    }
+}
+
+impl Default for InputState {
+    fn default() -> InputState { InputState::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_state_press_release() {
+        use { Button, Event, Input, Key, UpdateArgs };
+
+        let update: Event = Event::Update(UpdateArgs { dt: 0.0 });
+        let mut state = InputState::new();
+        state.event(&Event::Input(Input::Press(Button::Keyboard(Key::A).into())));
+        assert!(state.is_down(Key::A));
+        assert!(!state.just_pressed(Key::A));
+
+        state.event(&update);
+        assert!(state.just_pressed(Key::A));
+        state.event(&Event::Input(Input::Press(Button::Keyboard(Key::A).into())));
+        state.event(&Event::Input(Input::Release(Button::Keyboard(Key::A).into())));
+        assert!(!state.is_down(Key::A));
+        assert!(state.just_pressed(Key::A));
+
+        state.event(&update);
+        assert!(!state.just_pressed(Key::A));
+        assert!(state.just_released(Key::A));
+
+        state.event(&update);
+        assert!(!state.just_released(Key::A));
+    }
+
+    #[test]
+    fn test_input_state_motion() {
+        use { Event, Input, Motion, MouseRelativeArgs, ScrollArgs, ScrollUnit, UpdateArgs };
+
+        let update: Event = Event::Update(UpdateArgs { dt: 0.0 });
+        let mut state = InputState::new();
+        assert_eq!(state.cursor(), None);
+        state.event(&Event::Input(Input::Move(Motion::MouseCursor((1.0, 2.0).into()))));
+        state.event(&Event::Input(Input::Move(Motion::MouseScroll(ScrollArgs::new(0.0, 1.0)))));
+        state.event(&Event::Input(Input::Move(Motion::MouseScroll(
+            ScrollArgs::new(0.0, 2.0).unit(ScrollUnit::Pixels)))));
+        state.event(&Event::Input(Input::Move(Motion::MouseRelative(
+            MouseRelativeArgs::new(1.0, 1.0)))));
+        assert_eq!(state.cursor(), Some([1.0, 2.0]));
+        assert_eq!(state.scroll(), [0.0, 0.0]);
+
+        state.event(&update);
+        assert_eq!(state.scroll(), [0.0, 3.0]);
+        assert_eq!(state.relative(), [1.0, 1.0]);
+
+        state.event(&update);
+        assert_eq!(state.scroll(), [0.0, 0.0]);
+        assert_eq!(state.relative(), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_input_state_devices() {
+        use { Button, ButtonArgs, Event, Input, Key, UpdateArgs };
+
+        let update: Event = Event::Update(UpdateArgs { dt: 0.0 });
+        let key = |device| ButtonArgs::new(Button::Keyboard(Key::A)).device(device);
+        let mut state = InputState::new();
+        state.event(&Event::Input(Input::Press(key(0))));
+        state.event(&Event::Input(Input::Press(key(1))));
+        state.event(&Event::Input(Input::Release(key(0))));
+        assert!(state.is_down(Key::A));
+        state.event(&update);
+        assert!(state.just_pressed(Key::A));
+        assert!(!state.just_released(Key::A));
+
+        state.event(&Event::Input(Input::Release(key(1))));
+        assert!(!state.is_down(Key::A));
+        state.event(&update);
+        assert!(state.just_released(Key::A));
+    }
+
+    #[test]
+    fn test_input_state_focus() {
+        use { Button, Event, Input, MouseButton, UpdateArgs };
+
+        let update: Event = Event::Update(UpdateArgs { dt: 0.0 });
+        let mut state = InputState::new();
+        state.event(&Event::Input(Input::Press(Button::Mouse(MouseButton::Left).into())));
+        state.event(&Event::Input(Input::Focus(false)));
+        assert!(!state.focus());
+        assert!(!state.is_down(MouseButton::Left));
+
+        state.event(&update);
+        assert!(state.just_released(MouseButton::Left));
+    }
+
+    #[test]
+    fn test_input_state_controller_disconnected() {
+        use { Button, ControllerButton, ControllerConnection, Event, Input, UpdateArgs };
+
+        let update: Event = Event::Update(UpdateArgs { dt: 0.0 });
+        let mut state = InputState::new();
+        state.event(&Event::Input(Input::Press(Button::Controller(
+            ControllerButton::new(0, 1)).into())));
+        state.event(&Event::Input(Input::Press(Button::Controller(
+            ControllerButton::new(1, 1)).into())));
+        state.event(&Event::Input(Input::ControllerConnection(
+            ControllerConnection::Disconnected { id: 0 })));
+        assert!(!state.is_down(ControllerButton::new(0, 1)));
+        assert!(state.is_down(ControllerButton::new(1, 1)));
+
+        state.event(&update);
+        assert!(state.just_released(ControllerButton::new(0, 1)));
+    }
+}
@@ -1,7 +1,8 @@
 use std::borrow::ToOwned;
 use std::any::Any;
 
-use { GenericEvent, TEXT };
+use { ArgsError, GenericEvent, TEXT };
+use generic_event::try_with_args;
 
 /// When receiving text from user, such as typing a character
 pub trait TextEvent: Sized {
@@ -14,6 +15,8 @@ pub trait TextEvent: Sized {
     fn text_args(&self) -> Option<String> {
         self.text(|text| text.to_owned())
     }
+    /// Returns text arguments, or an error if they have an unexpected type.
+    fn try_text_args(&self) -> Result<Option<String>, ArgsError>;
 }
 
 impl<T: GenericEvent> TextEvent for T {
@@ -24,16 +27,12 @@ impl<T: GenericEvent> TextEvent for T {
     fn text<U, F>(&self, mut f: F) -> Option<U>
         where F: FnMut(&str) -> U
     {
-        if self.event_id() != TEXT {
-            return None;
-        }
-        self.with_args(|any| {
-            if let Some(text) = any.downcast_ref::<String>() {
-                Some(f(&text))
-            } else {
-                panic!("Expected &str")
-            }
-        })
+        try_with_args(self, TEXT, "String", |text: &String| f(text))
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_text_args(&self) -> Result<Option<String>, ArgsError> {
+        try_with_args(self, TEXT, "String", |text: &String| text.clone())
     }
 }
 
@@ -0,0 +1,323 @@
+//! An editable single line of text.
+//!
+//! Positions are byte offsets into the text,
+//! always at the boundary of an extended grapheme cluster as defined by Unicode,
+//! so a letter with combining marks, an emoji sequence or a flag is edited as a whole.
+
+use std::default::Default;
+use unicode_segmentation::GraphemeCursor;
+
+use { Button, ButtonArgs, GenericEvent, Key };
+use keyboard::{ ModifierKey, CTRL, NO_MODIFIER, SHIFT };
+
+/// Editable text with a cursor and selection, driven by input events.
+///
+/// - Text events insert text, replacing the selection
+/// - Backspace and Delete remove the selection or one grapheme cluster
+/// - Left and Right move the cursor by grapheme cluster
+/// - Home and End move the cursor to the start or end
+/// - With Ctrl held, movement and deletion is by word
+/// - With Shift held, movement extends the selection
+#[derive(Clone, Debug)]
+pub struct TextInput {
+    text: String,
+    cursor: usize,
+    anchor: usize,
+    modifiers: ModifierKey,
+}
+
+impl TextInput {
+    /// Creates a new empty text input.
+    pub fn new() -> TextInput {
+        TextInput {
+            text: String::new(),
+            cursor: 0,
+            anchor: 0,
+            modifiers: NO_MODIFIER,
+        }
+    }
+
+    /// Returns the text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Sets the text, moving the cursor to the end.
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_owned();
+        self.cursor = self.text.len();
+        self.anchor = self.cursor;
+    }
+
+    /// Returns the cursor position.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Returns the start and end of the selection, if any text is selected.
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        if self.anchor == self.cursor {
+            None
+        } else if self.anchor < self.cursor {
+            Some((self.anchor, self.cursor))
+        } else {
+            Some((self.cursor, self.anchor))
+        }
+    }
+
+    /// Returns the selected text.
+    pub fn selected_text(&self) -> &str {
+        match self.selection() {
+            Some((start, end)) => &self.text[start..end],
+            None => "",
+        }
+    }
+
+    /// Selects text from the anchor to the cursor.
+    ///
+    /// Panics if a position is not at the boundary of a grapheme cluster.
+    pub fn select(&mut self, anchor: usize, cursor: usize) {
+        assert!(is_boundary(&self.text, anchor) && is_boundary(&self.text, cursor),
+                "Expected position at grapheme cluster boundary");
+        self.anchor = anchor;
+        self.cursor = cursor;
+    }
+
+    /// Selects all text.
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.cursor = self.text.len();
+    }
+
+    /// Inserts text at the cursor, replacing the selection.
+    pub fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        self.text.insert_str(self.cursor, text);
+        self.cursor += text.len();
+        self.anchor = self.cursor;
+    }
+
+    /// Change text depending on event.
+    ///
+    /// Returns true if the text changed.
+    /// Events with arguments of an unexpected type are ignored.
+    pub fn event<E: GenericEvent>(&mut self, e: &E) -> bool {
+        use { PressEvent, TextEvent };
+
+        self.modifiers.event(e);
+        if let Ok(Some(text)) = e.try_text_args() {
+            let text: String = text.chars().filter(|c| !c.is_control()).collect();
+            if text.is_empty() { return false; }
+            self.insert(&text);
+            return true;
+        }
+        let key = match e.try_press_button_args() {
+            Ok(Some(ButtonArgs { button: Button::Keyboard(key), .. })) => key,
+            _ => return false,
+        };
+        let word = self.modifiers.contains(CTRL);
+        let select = self.modifiers.contains(SHIFT);
+        match key {
+            Key::Backspace => {
+                if self.delete_selection() { return true; }
+                let start = if word {
+                    prev_word(&self.text, self.cursor)
+                } else {
+                    prev_boundary(&self.text, self.cursor)
+                };
+                let end = self.cursor;
+                self.delete(start, end)
+            }
+            Key::Delete => {
+                if self.delete_selection() { return true; }
+                let end = if word {
+                    next_word(&self.text, self.cursor)
+                } else {
+                    next_boundary(&self.text, self.cursor)
+                };
+                let start = self.cursor;
+                self.delete(start, end)
+            }
+            Key::Left => {
+                let pos = match self.selection() {
+                    Some((start, _)) if !select => start,
+                    _ if word => prev_word(&self.text, self.cursor),
+                    _ => prev_boundary(&self.text, self.cursor),
+                };
+                self.move_cursor(pos, select);
+                false
+            }
+            Key::Right => {
+                let pos = match self.selection() {
+                    Some((_, end)) if !select => end,
+                    _ if word => next_word(&self.text, self.cursor),
+                    _ => next_boundary(&self.text, self.cursor),
+                };
+                self.move_cursor(pos, select);
+                false
+            }
+            Key::Home => {
+                self.move_cursor(0, select);
+                false
+            }
+            Key::End => {
+                let end = self.text.len();
+                self.move_cursor(end, select);
+                false
+            }
+            _ => false
+        }
+    }
+
+    fn move_cursor(&mut self, pos: usize, select: bool) {
+        self.cursor = pos;
+        if !select {
+            self.anchor = pos;
+        }
+    }
+
+    // Returns true if the text changed.
+    fn delete(&mut self, start: usize, end: usize) -> bool {
+        if start == end { return false; }
+        self.text.drain(start..end);
+        self.cursor = start;
+        self.anchor = start;
+        true
+    }
+
+    // Returns true if the text changed.
+    fn delete_selection(&mut self) -> bool {
+        match self.selection() {
+            Some((start, end)) => self.delete(start, end),
+            None => false,
+        }
+    }
+}
+
+impl Default for TextInput {
+    fn default() -> TextInput { TextInput::new() }
+}
+
+fn is_boundary(text: &str, pos: usize) -> bool {
+    text.is_char_boundary(pos) &&
+    GraphemeCursor::new(pos, text.len(), true).is_boundary(text, 0).unwrap_or(false)
+}
+
+// Returns the end of the grapheme cluster starting at the position.
+fn next_boundary(text: &str, pos: usize) -> usize {
+    let mut cursor = GraphemeCursor::new(pos, text.len(), true);
+    match cursor.next_boundary(text, 0) {
+        Ok(Some(end)) => end,
+        _ => text.len(),
+    }
+}
+
+// Returns the start of the grapheme cluster ending at the position.
+fn prev_boundary(text: &str, pos: usize) -> usize {
+    let mut cursor = GraphemeCursor::new(pos, text.len(), true);
+    match cursor.prev_boundary(text, 0) {
+        Ok(Some(start)) => start,
+        _ => 0,
+    }
+}
+
+fn is_word(grapheme: &str) -> bool {
+    grapheme.chars().next().map_or(false, |c| c.is_alphanumeric() || c == '_')
+}
+
+// Returns the start of the word before the position.
+fn prev_word(text: &str, mut pos: usize) -> usize {
+    while pos > 0 {
+        let start = prev_boundary(text, pos);
+        if is_word(&text[start..pos]) { break; }
+        pos = start;
+    }
+    while pos > 0 {
+        let start = prev_boundary(text, pos);
+        if !is_word(&text[start..pos]) { break; }
+        pos = start;
+    }
+    pos
+}
+
+// Returns the end of the word after the position.
+fn next_word(text: &str, mut pos: usize) -> usize {
+    while pos < text.len() {
+        let end = next_boundary(text, pos);
+        if is_word(&text[pos..end]) { break; }
+        pos = end;
+    }
+    while pos < text.len() {
+        let end = next_boundary(text, pos);
+        if !is_word(&text[pos..end]) { break; }
+        pos = end;
+    }
+    pos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_input_editing() {
+        use { Button, Event, Input, Key };
+
+        let press = |key| -> Event { Event::Input(Input::Press(Button::Keyboard(key).into())) };
+        let release = |key| -> Event { Event::Input(Input::Release(Button::Keyboard(key).into())) };
+        let text = |s: &str| -> Event { Event::Input(Input::Text(s.into())) };
+
+        let mut input = TextInput::new();
+        assert!(input.event(&text("hello world")));
+        assert!(!input.event(&text("\u{8}")));
+        input.event(&press(Key::LCtrl));
+        input.event(&press(Key::Left));
+        assert_eq!(input.cursor(), 6);
+        assert!(input.event(&press(Key::Backspace)));
+        assert_eq!(input.text(), "world");
+        input.event(&release(Key::LCtrl));
+
+        input.event(&press(Key::LShift));
+        input.event(&press(Key::End));
+        assert_eq!(input.selected_text(), "world");
+        input.event(&release(Key::LShift));
+        input.event(&text("there"));
+        assert_eq!(input.text(), "there");
+        input.event(&press(Key::Home));
+        input.event(&press(Key::Delete));
+        assert_eq!(input.text(), "here");
+        assert_eq!(input.selection(), None);
+    }
+
+    #[test]
+    fn test_text_input_graphemes() {
+        use { Button, Event, Input, Key };
+
+        let press = |key| -> Event { Event::Input(Input::Press(Button::Keyboard(key).into())) };
+
+        let mut input = TextInput::new();
+        // An e with combining acute accent, a family emoji and a flag.
+        input.set_text("ae\u{301}\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{1F1F3}\u{1F1F4}");
+        input.event(&press(Key::Backspace));
+        assert_eq!(input.text(), "ae\u{301}\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}");
+        input.event(&press(Key::Backspace));
+        assert_eq!(input.text(), "ae\u{301}");
+        input.event(&press(Key::Left));
+        assert_eq!(input.cursor(), 1);
+        input.event(&press(Key::Delete));
+        assert_eq!(input.text(), "a");
+    }
+
+    #[test]
+    fn test_text_input_select() {
+        let mut input = TextInput::new();
+        // Two flags, made of regional indicators.
+        input.set_text("\u{1F1F3}\u{1F1F4}\u{1F1E9}\u{1F1EA}");
+        input.select(0, 8);
+        assert_eq!(input.selected_text(), "\u{1F1F3}\u{1F1F4}");
+        assert!(is_boundary(input.text(), 16));
+        assert!(!is_boundary(input.text(), 4));
+        assert!(!is_boundary(input.text(), 12));
+        assert!(!is_boundary(input.text(), 2));
+    }
+}
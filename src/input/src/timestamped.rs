@@ -0,0 +1,78 @@
+//! Input events with the time they happened.
+
+use std::any::Any;
+
+use { ArgsError, EventId, GenericEvent };
+
+/// Wraps an input event with the time it happened.
+///
+/// The timestamp is in nanoseconds, on the same clock as `time::precise_time_ns`.
+/// Events created from a timestamped event keep its timestamp.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Timestamped<I> {
+    /// The input event.
+    pub input: I,
+    /// The time in nanoseconds.
+    pub timestamp: u64,
+}
+
+impl<I> Timestamped<I> {
+    /// Creates a new timestamped event.
+    pub fn new(input: I, timestamp: u64) -> Timestamped<I> {
+        Timestamped {
+            input: input,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl<I: GenericEvent> GenericEvent for Timestamped<I> {
+    fn event_id(&self) -> EventId {
+        self.input.event_id()
+    }
+
+    fn with_args<'a, F, U>(&'a self, f: F) -> U
+        where F: FnMut(&Any) -> U
+    {
+        self.input.with_args(f)
+    }
+
+    fn from_args(event_id: EventId, any: &Any, old_event: &Self) -> Option<Self> {
+        let input: Option<I> = GenericEvent::from_args(event_id, any, &old_event.input);
+        input.map(|input| Timestamped::new(input, old_event.timestamp))
+    }
+
+    fn try_from_args(event_id: EventId, any: &Any, old_event: &Self)
+        -> Result<Option<Self>, ArgsError>
+    {
+        let input: Option<I> =
+            try!(GenericEvent::try_from_args(event_id, any, &old_event.input));
+        Ok(input.map(|input| Timestamped::new(input, old_event.timestamp)))
+    }
+
+    fn timestamp(&self) -> Option<u64> {
+        Some(self.timestamp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamped() {
+        use { Button, Event, GenericEvent, Input, Key, PressEvent, UpdateArgs };
+
+        let e: Event<Timestamped<Input>> = Event::Input(Timestamped::new(
+            Input::Press(Button::Keyboard(Key::S).into()), 1000));
+        assert_eq!(e.timestamp(), Some(1000));
+        assert_eq!(e.press_args(), Some(Button::Keyboard(Key::S)));
+        let x: Option<Event<Timestamped<Input>>> =
+            PressEvent::from_button(Button::Keyboard(Key::A), &e);
+        assert_eq!(x, Some(Event::Input(Timestamped::new(
+            Input::Press(Button::Keyboard(Key::A).into()), 1000))));
+
+        let update: Event = Event::Update(UpdateArgs { dt: 0.0 });
+        assert_eq!(update.timestamp(), None);
+    }
+}
@@ -1,6 +1,7 @@
 use std::any::Any;
 
-use { GenericEvent, TOUCH };
+use { ArgsError, GenericEvent, TOUCH };
+use generic_event::try_with_args;
 
 /// Stores the touch state.
 #[derive(Copy, Clone, RustcDecodable, RustcEncodable, PartialEq, Debug)]
@@ -132,6 +133,8 @@ pub trait TouchEvent: Sized {
     fn touch_args(&self) -> Option<TouchArgs> {
         self.touch(|args| args.clone())
     }
+    /// Returns touch arguments, or an error if they have an unexpected type.
+    fn try_touch_args(&self) -> Result<Option<TouchArgs>, ArgsError>;
 }
 
 impl<T> TouchEvent for T where T: GenericEvent {
@@ -139,19 +142,15 @@ impl<T> TouchEvent for T where T: GenericEvent {
         GenericEvent::from_args(TOUCH, args as &Any, old_event)
     }
 
-    fn touch<U, F>(&self, mut f: F) -> Option<U>
+    fn touch<U, F>(&self, f: F) -> Option<U>
         where F: FnMut(&TouchArgs) -> U
     {
-        if self.event_id() != TOUCH {
-            return None;
-        }
-        self.with_args(|any| {
-            if let Some(args) = any.downcast_ref::<TouchArgs>() {
-                Some(f(args))
-            } else {
-                panic!("Expected TouchArgs")
-            }
-        })
+        try_with_args(self, TOUCH, "TouchArgs", f)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_touch_args(&self) -> Result<Option<TouchArgs>, ArgsError> {
+        try_with_args(self, TOUCH, "TouchArgs", |&args: &TouchArgs| args)
     }
 }
 
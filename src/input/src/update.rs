@@ -1,6 +1,7 @@
 use std::any::Any;
 
-use { GenericEvent, UPDATE };
+use { ArgsError, GenericEvent, UPDATE };
+use generic_event::try_with_args;
 
 /// Update arguments, such as delta time in seconds
 #[derive(Copy, Clone, PartialEq, Debug)]
@@ -24,6 +25,8 @@ pub trait UpdateEvent: Sized {
     fn update_args(&self) -> Option<UpdateArgs> {
         self.update(|args| args.clone())
     }
+    /// Returns update arguments, or an error if they have an unexpected type.
+    fn try_update_args(&self) -> Result<Option<UpdateArgs>, ArgsError>;
 }
 
 impl<T> UpdateEvent for T where T: GenericEvent {
@@ -31,19 +34,15 @@ impl<T> UpdateEvent for T where T: GenericEvent {
         GenericEvent::from_args(UPDATE, args as &Any, old_event)
     }
 
-    fn update<U, F>(&self, mut f: F) -> Option<U>
+    fn update<U, F>(&self, f: F) -> Option<U>
         where F: FnMut(&UpdateArgs) -> U
     {
-        if self.event_id() != UPDATE {
-            return None;
-        }
-        self.with_args(|any| {
-            if let Some(args) = any.downcast_ref::<UpdateArgs>() {
-                Some(f(args))
-            } else {
-                panic!("Expected UpdateArgs")
-            }
-        })
+        try_with_args(self, UPDATE, "UpdateArgs", f)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_update_args(&self) -> Result<Option<UpdateArgs>, ArgsError> {
+        try_with_args(self, UPDATE, "UpdateArgs", |&args: &UpdateArgs| args)
     }
 }
 
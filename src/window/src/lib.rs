@@ -15,6 +15,9 @@
 //! new windows in Piston. It uses the `BuildFromWindowSettings` trait,
 //! which backends implement to handle window creation and setup.
 //!
+//! The [`ClipboardWindow`](./trait.ClipboardWindow.html) trait gives access
+//! to the clipboard of the platform, for copy and paste.
+//!
 //! The [`OpenGLWindow`](./trait.OpenGLWindow.html) trait is used to provide low-level
 //! access to OpenGL through the abstract Piston API.
 //!
@@ -154,6 +157,16 @@ pub trait Window {
     /// [`Events`](../event_loop/trait.Events.html) trait instead.
     fn poll_event(&mut self) -> Option<Self::Event>;
 
+    /// Polls an event from the window, with the time it happened if known.
+    ///
+    /// The time is in nanoseconds, on the same clock as `time::precise_time_ns`.
+    /// Backends that get timestamps from the platform should override this.
+    /// By default, no timestamp is returned and the event loop
+    /// uses the time the event was polled.
+    fn poll_event_timestamped(&mut self) -> Option<(Self::Event, Option<u64>)> {
+        self.poll_event().map(|e| (e, None))
+    }
+
     /// Gets the draw size of the window.
     ///
     /// This is equal to the size of the frame buffer of the inner window,
@@ -256,6 +269,69 @@ pub trait AdvancedWindow: Window + Sized {
         self.set_position(val);
         self
     }
+
+    /// Sets the position of the input method editor candidate window,
+    /// relative to the window.
+    ///
+    /// Call this with the position of the text cursor while composing text,
+    /// so the candidates are shown next to the text.
+    /// If the platform does not support this, it will have no effect.
+    /// The default implementation does nothing.
+    fn set_ime_position<P: Into<Position>>(&mut self, _pos: P) {}
+}
+
+/// An image stored on the clipboard.
+#[derive(Debug, Clone)]
+pub struct ClipboardImage {
+    /// The size of the image in pixels.
+    pub size: Size,
+    /// The pixels as 8-bit RGBA, row by row from the top.
+    pub data: Vec<u8>,
+}
+
+impl ClipboardImage {
+    /// Checks that the data has 4 bytes for each pixel.
+    pub fn validate(&self) -> Result<(), String> {
+        let expected = self.size.width as usize * self.size.height as usize * 4;
+        if self.data.len() == expected {
+            Ok(())
+        } else {
+            Err(format!("Expected {} bytes of RGBA data for a {}x{} image, found {}",
+                        expected, self.size.width, self.size.height, self.data.len()))
+        }
+    }
+}
+
+/// Trait for accessing the clipboard of the platform.
+///
+/// Backends implement this next to [`AdvancedWindow`](./trait.AdvancedWindow.html).
+/// Text is required, while images are optional.
+pub trait ClipboardWindow: Window {
+    /// Gets the text on the clipboard.
+    ///
+    /// Returns `None` if the clipboard is empty or does not contain text.
+    fn get_clipboard_text(&self) -> Option<String>;
+
+    /// Puts text on the clipboard.
+    fn set_clipboard_text(&mut self, text: &str) -> Result<(), String>;
+
+    /// Gets the image on the clipboard.
+    ///
+    /// Returns `None` if the clipboard does not contain an image,
+    /// or if the backend does not support images.
+    fn get_clipboard_image(&self) -> Option<ClipboardImage> { None }
+
+    /// Puts an image on the clipboard.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image data does not match its size,
+    /// see [`ClipboardImage::validate`](./struct.ClipboardImage.html#method.validate).
+    /// The default implementation returns an error,
+    /// for backends that do not support images.
+    fn set_clipboard_image(&mut self, _image: &ClipboardImage) -> Result<(), String> {
+        Err("Clipboard images are not supported by this backend".to_owned())
+    }
 }
 
 /// Trait for OpenGL specific operations on a window.
@@ -13,6 +13,8 @@ use {
 	WindowSettings,
 	BuildFromWindowSettings,
 	AdvancedWindow,
+	ClipboardImage,
+	ClipboardWindow,
 	Position,
 	Size
 };
@@ -25,11 +27,14 @@ use self::input::Input;
 /// The size can be changed because the event loop does not emit
 /// [`Render`](../event_loop/trait.RenderEvent.html)
 /// events when the width or height is zero.
+/// The clipboard is stored in memory.
 pub struct NoWindow {
     should_close: bool,
     title: String,
     size: Size,
-	pos: Position
+	pos: Position,
+	clipboard_text: Option<String>,
+	clipboard_image: Option<ClipboardImage>
 }
 
 impl NoWindow {
@@ -39,7 +44,9 @@ impl NoWindow {
             should_close: false,
             title: settings.get_title(),
             size: settings.get_size(),
-			pos: Position { x: 0, y: 0 }
+			pos: Position { x: 0, y: 0 },
+			clipboard_text: None,
+			clipboard_image: None
         }
     }
 }
@@ -89,4 +96,49 @@ impl AdvancedWindow for NoWindow {
 	fn set_position<P: Into<Position>>(&mut self, val: P) {
 		self.pos = val.into();
 	}
+
+	fn set_ime_position<P: Into<Position>>(&mut self, _val: P) {}
+}
+
+impl ClipboardWindow for NoWindow {
+	fn get_clipboard_text(&self) -> Option<String> { self.clipboard_text.clone() }
+
+	fn set_clipboard_text(&mut self, text: &str) -> Result<(), String> {
+		self.clipboard_text = Some(text.to_owned());
+		Ok(())
+	}
+
+	fn get_clipboard_image(&self) -> Option<ClipboardImage> { self.clipboard_image.clone() }
+
+	fn set_clipboard_image(&mut self, image: &ClipboardImage) -> Result<(), String> {
+		try!(image.validate());
+		self.clipboard_image = Some(image.clone());
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_clipboard_text() {
+		let mut window = NoWindow::new(&WindowSettings::new("test", [20, 10]));
+		assert_eq!(window.get_clipboard_text(), None);
+		window.set_clipboard_text("hello").unwrap();
+		assert_eq!(window.get_clipboard_text(), Some("hello".to_owned()));
+	}
+
+	#[test]
+	fn test_clipboard_image() {
+		let mut window = NoWindow::new(&WindowSettings::new("test", [20, 10]));
+		assert!(window.get_clipboard_image().is_none());
+		let size = Size { width: 2, height: 1 };
+		let image = ClipboardImage { size: size, data: vec![255; 8] };
+		window.set_clipboard_image(&image).unwrap();
+		assert_eq!(window.get_clipboard_image().unwrap().data, image.data);
+		let bad = ClipboardImage { size: size, data: vec![255; 7] };
+		assert!(window.set_clipboard_image(&bad).is_err());
+		assert_eq!(window.get_clipboard_image().unwrap().data, image.data);
+	}
 }